@@ -15,7 +15,10 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_systems(Startup, startup)
         .add_systems(PreUpdate, control_shape)
-        .add_systems(Update, (update_shape_transforms, update_color).chain())
+        .add_systems(
+            Update,
+            (update_shape_transforms, update_color, draw_outlines).chain(),
+        )
         .run();
 }
 
@@ -130,6 +133,15 @@ fn update_color(
     }
 }
 
+/// Draw the outline of the collision shapes (as seen by the collision detection) on top of the sprites
+fn draw_outlines(mut gizmos: Gizmos, shapes: Query<&CollisionShape>) {
+    for shape in shapes.iter() {
+        let outline: Vec<Vec2> = shape.outline(1.0).map(Vec2::from).collect();
+        let closed = outline.iter().copied().chain(outline.first().copied());
+        gizmos.linestrip_2d(closed, Color::WHITE);
+    }
+}
+
 /// Simple control system to move the shape with the arrows keys, and rotate with `A` and `D`
 /// (Nothing specific about collision detection here)
 fn control_shape(
@@ -53,9 +53,34 @@
 //! ## Feature flags
 //!
 //! * `std` (enabled by default) Allow to use rust the standard library (need to be disabled for `no_std` apps)
+//!   Without it, the crate still needs the `alloc` crate (and a global allocator)
 //! * `bvh-arena` Integration with [bvh-arena](https://crates.io/crates/bvh-arena) bounding volumes
+//! * `mint` Conversions between the vector types of this crate and [mint](https://crates.io/crates/mint)
+//!   (the `[f32; 2]` inputs and outputs of the API already convert to and from `mint::Vector2` and `mint::Point2`)
+//! * `nalgebra` Conversions from nalgebra's `Isometry2`, `Similarity2` and `Affine2` to [`Transform`] (and back to `Affine2`)
+//!   (the `[f32; 2]` inputs and outputs of the API already convert to and from nalgebra's `Point2` and `Vector2`)
+//! * `geo` Conversions from the [geo](https://crates.io/crates/geo) `Line`, `Triangle`, `Rect`, `Polygon` and `LineString` types
+//!   (concave polygons become a [`CompoundShape`]), and [`CollisionShape::to_geo`] to export shapes back
+//! * `tiled` Import of the object layers of [Tiled](https://www.mapeditor.org) maps (see [`tiled`])
+//! * `svg` Import of the shape elements of SVG documents, with their transforms (see [`svg`])
+//! * `debug-svg` [`DebugScene`] rendering shapes, contacts and the GJK state as an SVG image, for debugging
+//! * `serde` Implementations of `Serialize` and `Deserialize` for the shapes, transforms and query results
+//! * `deterministic` Compute the vector and matrix operations without SIMD, for bit-identical `f32` results (see below)
 //!
 //!
+//! ## Determinism
+//!
+//! The transcendental functions and square roots are always computed by [libm](https://crates.io/crates/libm)
+//! (instead of the platform implementation), and the crate never uses fused multiply-add.
+//! With the `deterministic` feature, the vector and matrix operations are also computed without SIMD,
+//! so that the `f32` queries give bit-identical results on every target with IEEE-754 single-precision arithmetic
+//! (which excludes the x87 FPU of 32-bit x86 targets without SSE2).
+//!
+//! The results are only guaranteed to be identical when all peers use the same versions of this crate and of libm,
+//! as a new version of libm may change the last bits of its results. (A lockfile pinning libm is recommended.)
+//!
+//! For determinism without floating-point numbers, see [`FixedCollisionShape`].
+//!
 //! ## Unstable feature flags
 //!
 //! **The following features may receive breaking changes or be removed in a patch release!**
@@ -64,7 +89,6 @@
 //! * `unstable-v3-aabb` Axis-Aligned-Bounding-Box shape for the v3 module
 //!
 
-#[cfg(all(test, feature = "unstable-v3-aabb"))]
 extern crate alloc;
 
 mod v2;
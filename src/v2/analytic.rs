@@ -0,0 +1,282 @@
+use glam::Vec2;
+
+use super::{
+    shapes::{Circle, Rectangle, ShapeData},
+    CollisionShape, Contact, Feature, Transform,
+};
+
+/// Result of a query between two shapes, solved in closed form
+pub(super) enum ClosedForm {
+    /// There is no closed form for this pair of shapes (and transforms)
+    Unsupported,
+    /// The shapes don't collide
+    Separated,
+    /// The shapes collide
+    Contact(Contact),
+}
+
+impl ClosedForm {
+    /// Swap the roles of the two shapes
+    fn flipped(self) -> Self {
+        match self {
+            Self::Contact(Contact {
+                normal,
+                penetration,
+                features: [feature1, feature2],
+            }) => Self::Contact(Contact {
+                normal: (-Vec2::from(normal)).into(),
+                penetration,
+                features: [feature2, feature1],
+            }),
+            other => other,
+        }
+    }
+}
+
+impl CollisionShape {
+    /// Returns the contact with `other`, if this pair of shapes can be solved without running GJK and EPA
+    ///
+    /// Supported pairs are circle-circle, circle-rectangle and axis-aligned rectangles.
+    /// Circles must not be deformed by their transform (no skew nor non-uniform scale).
+    pub(super) fn closed_form_contact(&self, other: &Self) -> ClosedForm {
+        match (&self.data, &other.data) {
+            (ShapeData::Circle(circle1), ShapeData::Circle(circle2)) => {
+                match (
+                    WorldCircle::new(*circle1, &self.transform),
+                    WorldCircle::new(*circle2, &other.transform),
+                ) {
+                    (Some(circle1), Some(circle2)) => circle1.contact_with_circle(&circle2),
+                    _ => ClosedForm::Unsupported,
+                }
+            }
+            (ShapeData::Circle(circle), ShapeData::Rectangle(rect)) => {
+                WorldCircle::new(*circle, &self.transform).map_or(ClosedForm::Unsupported, |c| {
+                    c.contact_with_rectangle(*rect, &other.transform)
+                })
+            }
+            (ShapeData::Rectangle(rect), ShapeData::Circle(circle)) => {
+                WorldCircle::new(*circle, &other.transform).map_or(ClosedForm::Unsupported, |c| {
+                    c.contact_with_rectangle(*rect, &self.transform).flipped()
+                })
+            }
+            (ShapeData::Rectangle(rect1), ShapeData::Rectangle(rect2)) => {
+                axis_aligned_rectangles(*rect1, &self.transform, *rect2, &other.transform)
+            }
+            _ => ClosedForm::Unsupported,
+        }
+    }
+}
+
+struct WorldCircle {
+    center: Vec2,
+    radius: f32,
+}
+
+impl WorldCircle {
+    fn new(circle: Circle, transform: &Transform) -> Option<Self> {
+        Some(Self {
+            center: transform.position(),
+            radius: circle.radius() * transform.uniform_scale()?,
+        })
+    }
+
+    fn contact_with_circle(&self, other: &Self) -> ClosedForm {
+        let offset = self.center - other.center;
+        let Some(normal) = offset.try_normalize() else {
+            return ClosedForm::Unsupported;
+        };
+        contact_if_penetrating(
+            normal,
+            self.radius + other.radius - offset.length(),
+            [Feature::Surface, Feature::Surface],
+        )
+    }
+
+    /// The rectangle may be rotated (but neither skewed nor non-uniformly scaled)
+    fn contact_with_rectangle(&self, rect: Rectangle, transform: &Transform) -> ClosedForm {
+        let Some(scale) = transform.uniform_scale() else {
+            return ClosedForm::Unsupported;
+        };
+        let half_extents = Vec2::from(rect.half_extents());
+        let center = *transform.world_to_local() * (self.center - transform.position());
+        let radius = self.radius / scale;
+        let closest = center.clamp(-half_extents, half_extents);
+        let (normal, penetration, feature) = if closest == center {
+            let depth = half_extents - center.abs();
+            let normal = if depth.x < depth.y {
+                Vec2::new(center.x.signum(), 0.0)
+            } else {
+                Vec2::new(0.0, center.y.signum())
+            };
+            (
+                normal,
+                depth.min_element() + radius,
+                Feature::rectangle_edge(normal),
+            )
+        } else {
+            let offset = center - closest;
+            let outside = center.abs().cmpgt(half_extents);
+            let feature = match (outside.x, outside.y) {
+                (true, false) => Feature::rectangle_edge(Vec2::new(offset.x, 0.0)),
+                (false, true) => Feature::rectangle_edge(Vec2::new(0.0, offset.y)),
+                _ => Feature::rectangle_vertex(offset),
+            };
+            (offset.normalize(), radius - offset.length(), feature)
+        };
+        contact_if_penetrating(
+            (transform.local_to_world().matrix2 * normal).normalize(),
+            penetration * scale,
+            [Feature::Surface, feature],
+        )
+    }
+}
+
+fn axis_aligned_rectangles(
+    rect1: Rectangle,
+    transform1: &Transform,
+    rect2: Rectangle,
+    transform2: &Transform,
+) -> ClosedForm {
+    let (Some(scale1), Some(scale2)) = (
+        transform1.axis_aligned_scale(),
+        transform2.axis_aligned_scale(),
+    ) else {
+        return ClosedForm::Unsupported;
+    };
+    let offset = transform1.position() - transform2.position();
+    let depth = Vec2::from(rect1.half_extents()) * scale1.abs()
+        + Vec2::from(rect2.half_extents()) * scale2.abs()
+        - offset.abs();
+    let normal = if depth.x < depth.y {
+        Vec2::new(offset.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, offset.y.signum())
+    };
+    contact_if_penetrating(
+        normal,
+        depth.min_element(),
+        [
+            Feature::rectangle_edge(-normal * scale1.signum()),
+            Feature::rectangle_edge(normal * scale2.signum()),
+        ],
+    )
+}
+
+fn contact_if_penetrating(normal: Vec2, penetration: f32, features: [Feature; 2]) -> ClosedForm {
+    if penetration < 0.0 {
+        ClosedForm::Separated
+    } else {
+        ClosedForm::Contact(Contact {
+            normal: normal.into(),
+            penetration,
+            features,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::PairCache;
+
+    use super::*;
+
+    fn assert_equivalent_to_gjk(shape1: &CollisionShape, shape2: &CollisionShape) {
+        let expected = match shape1.closed_form_contact(shape2) {
+            ClosedForm::Unsupported => panic!("no closed form"),
+            ClosedForm::Separated => {
+                assert!(!shape1.is_collided_with_cached(shape2, &mut PairCache::new()));
+                return;
+            }
+            ClosedForm::Contact(contact) => contact,
+        };
+        let actual = shape1
+            .contact_with_cached(shape2, &mut PairCache::new())
+            .unwrap();
+        assert_abs_diff_eq!(actual.penetration, expected.penetration, epsilon = 1e-4);
+        assert_abs_diff_eq!(
+            Vec2::from(actual.normal),
+            Vec2::from(expected.normal),
+            epsilon = 0.01
+        );
+    }
+
+    fn positions() -> impl Iterator<Item = [f32; 2]> {
+        (0..15_u8).flat_map(|i| {
+            (0..15_u8).map(move |j| [f32::from(i) * 0.3 - 2.05, f32::from(j) * 0.3 - 2.05])
+        })
+    }
+
+    #[test]
+    fn circles() {
+        let circle = CollisionShape::new_circle(1.0);
+        for position in positions() {
+            let other = CollisionShape::new_circle(0.5).with_transform(
+                Transform::from_scale_angle_translation([1.5, 1.5], 0.3, position),
+            );
+            assert_equivalent_to_gjk(&circle, &other);
+        }
+    }
+
+    #[test]
+    fn circle_and_rectangle() {
+        let rect = CollisionShape::new_rectangle(2.0, 1.0).with_transform(
+            Transform::from_scale_angle_translation([2.0, 2.0], 0.5, [0.1, 0.0]),
+        );
+        for position in positions() {
+            let circle = CollisionShape::new_circle(0.5)
+                .with_transform(Transform::from_translation(position));
+            assert_equivalent_to_gjk(&circle, &rect);
+            assert_equivalent_to_gjk(&rect, &circle);
+        }
+    }
+
+    #[test]
+    fn axis_aligned_rectangles() {
+        let rect = CollisionShape::new_rectangle(2.0, 1.0).with_transform(
+            Transform::from_scale_angle_translation([-1.0, 2.0], 0.0, [0.1, 0.0]),
+        );
+        for position in positions() {
+            let other = CollisionShape::new_rectangle(1.0, 1.5)
+                .with_transform(Transform::from_translation(position));
+            assert_equivalent_to_gjk(&rect, &other);
+        }
+    }
+
+    #[test]
+    fn unsupported_transforms() {
+        let ellipse = CollisionShape::new_circle(1.0).with_transform(
+            Transform::from_scale_angle_translation([2.0, 1.0], 0.0, [0.0, 0.0]),
+        );
+        let rotated = CollisionShape::new_rectangle(1.0, 1.0)
+            .with_transform(Transform::from_angle_translation(0.3, [0.0, 0.0]));
+        let rect = CollisionShape::new_rectangle(1.0, 1.0);
+        assert!(matches!(
+            ellipse.closed_form_contact(&rect),
+            ClosedForm::Unsupported
+        ));
+        assert!(matches!(
+            rotated.closed_form_contact(&rect),
+            ClosedForm::Unsupported
+        ));
+    }
+
+    #[test]
+    fn features() {
+        let ground = CollisionShape::new_rectangle(10.0, 2.0)
+            .with_transform(Transform::from_translation([0.0, -1.0]));
+        let block = CollisionShape::new_rectangle(1.0, 1.0)
+            .with_transform(Transform::from_translation([0.3, 0.45]));
+        let ClosedForm::Contact(contact) = block.closed_form_contact(&ground) else {
+            panic!("no contact");
+        };
+        assert_eq!(contact.features, [Feature::Edge(0), Feature::Edge(2)]);
+        let ball =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([5.5, 0.5]));
+        let ClosedForm::Contact(contact) = ground.closed_form_contact(&ball) else {
+            panic!("no contact");
+        };
+        assert_eq!(contact.features, [Feature::Vertex(2), Feature::Surface]);
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+
+use glam::Vec2;
+
+use super::{
+    analytic::ClosedForm, epa, gjk, minkowski, shapes::ShapeData, CollisionShape, Contact, Feature,
+    QueryConfig, Support, SupportPoint,
+};
+
+impl CollisionShape {
+    /// Returns the contacts with each of the `others` shapes that collide with this one
+    ///
+    /// Each item is the index of the colliding shape in `others`, with the contact data
+    /// (in the same form as [`contact_with`](Self::contact_with)).
+    /// Shapes that don't collide are skipped.
+    ///
+    /// This is meant to be used after a broad-phase, to test one shape against all its candidates.
+    /// The transform of this shape is applied once (to its vertices),
+    /// and the memory used for contact generation is allocated once and reused for every candidate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::{CollisionShape, Transform};
+    /// let shape = CollisionShape::new_circle(1.0);
+    /// let candidates = [
+    ///     CollisionShape::new_rectangle(2.0, 2.0).with_transform(Transform::from_translation([1.5, 0.0])),
+    ///     CollisionShape::new_rectangle(2.0, 2.0).with_transform(Transform::from_translation([5.0, 0.0])),
+    ///     CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([0.0, -1.0])),
+    /// ];
+    /// let hits: Vec<usize> = shape.contacts_with(&candidates).map(|(index, _)| index).collect();
+    /// assert_eq!(hits, [0, 2]);
+    /// ```
+    pub fn contacts_with<'a, I>(&'a self, others: I) -> impl Iterator<Item = (usize, Contact)> + 'a
+    where
+        I: IntoIterator<Item = &'a CollisionShape>,
+        I::IntoIter: 'a,
+    {
+        self.contacts_with_config(others, &QueryConfig::default())
+    }
+
+    /// Same as [`contacts_with`](Self::contacts_with), with the given tolerance and iteration limits
+    pub fn contacts_with_config<'a, I>(
+        &'a self,
+        others: I,
+        config: &QueryConfig,
+    ) -> impl Iterator<Item = (usize, Contact)> + 'a
+    where
+        I: IntoIterator<Item = &'a CollisionShape>,
+        I::IntoIter: 'a,
+    {
+        let config = *config;
+        let world_shape = WorldSupport::new(self);
+        let mut polytope = epa::Simplex::new();
+        others
+            .into_iter()
+            .enumerate()
+            .filter_map(move |(index, other)| {
+                match self.closed_form_contact(other) {
+                    ClosedForm::Unsupported => (),
+                    ClosedForm::Separated => return None,
+                    ClosedForm::Contact(contact) => return Some((index, contact)),
+                }
+                let difference = minkowski::Difference {
+                    shape1: &world_shape,
+                    shape2: other,
+                };
+                let simplex = gjk::find_simplex_enclosing_origin(
+                    &difference,
+                    SupportPoint::from(self.initial_axis(other)),
+                    config.max_gjk_iterations,
+                )
+                .ok()?;
+                let penetration =
+                    epa::generate_contact_in(&mut polytope, &difference, simplex, &config);
+                Some((index, self.contact(other, penetration)))
+            })
+    }
+}
+
+/// Support function of a shape, with its transform applied once to its vertices
+///
+/// The support point is chosen exactly as by [`CollisionShape::support_feature`] (in local space),
+/// but a vertex is then looked up in world space instead of being transformed again.
+struct WorldSupport<'a> {
+    shape: &'a CollisionShape,
+    /// Vertices in world space, indexed by their feature identifier
+    ///
+    /// Empty for circles, and limited to the vertices having a distinct identifier.
+    vertices: Vec<Vec2>,
+}
+
+impl<'a> WorldSupport<'a> {
+    fn new(shape: &'a CollisionShape) -> Self {
+        let vertices: Vec<Vec2> = match &shape.data {
+            ShapeData::Circle(_) => Vec::new(),
+            ShapeData::Rectangle(rect) => {
+                let [x, y] = rect.half_extents();
+                [[-x, -y], [x, -y], [x, y], [-x, y]]
+                    .into_iter()
+                    .map(Vec2::from)
+                    .collect()
+            }
+            ShapeData::Segment(segment) => segment.points().into_iter().map(Vec2::from).collect(),
+            ShapeData::ConvexPolygon(polygon) => polygon.vertices_slice().to_vec(),
+        };
+        let transform = shape.transform.local_to_world();
+        Self {
+            shape,
+            vertices: vertices
+                .into_iter()
+                .take(usize::from(u8::MAX))
+                .map(|vertex| transform.transform_point2(vertex))
+                .collect(),
+        }
+    }
+
+    fn support_feature(&self, direction: Vec2) -> (Vec2, Feature) {
+        let local_direction = self.shape.transform.direction_to_local(direction);
+        let (local_support, feature) = self.shape.data.support_feature(local_direction);
+        let vertex = match feature {
+            Feature::Vertex(index) => self.vertices.get(usize::from(index)).copied(),
+            Feature::Edge(_) | Feature::Surface => None,
+        };
+        let support = vertex.unwrap_or_else(|| {
+            self.shape
+                .transform
+                .local_to_world()
+                .transform_point2(local_support)
+        });
+        (support, feature)
+    }
+}
+
+impl Support<SupportPoint> for minkowski::Difference<'_, WorldSupport<'_>, CollisionShape> {
+    fn support(&self, direction: SupportPoint) -> SupportPoint {
+        let (p1, f1) = self.shape1.support_feature(direction.point);
+        let (p2, f2) = self.shape2.support_feature(-direction.point);
+        SupportPoint::new(p1 - p2, [f1, f2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_ulps_eq;
+    use glam::Vec2;
+
+    use crate::Transform;
+
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let shape = CollisionShape::new_circle(1.0);
+        assert_eq!(shape.contacts_with(&[]).count(), 0);
+    }
+
+    #[test]
+    fn same_contacts_as_individual_queries() {
+        let transform = Transform::from_scale_angle_translation([1.0, 1.5], 0.3, [0.5, 0.0]);
+        for shape in [
+            CollisionShape::new_rectangle(2.0, 2.0),
+            CollisionShape::new_circle(1.0),
+            CollisionShape::new_segment([-1.0, -0.5], [1.0, 0.5]),
+            CollisionShape::new_convex_polygon([[-1.0, -1.0], [1.0, -0.5], [0.5, 1.0]]),
+        ] {
+            assert_same_contacts_as_individual_queries(&shape.with_transform(transform.clone()));
+        }
+    }
+
+    #[test]
+    fn empty_polygon_has_same_contacts_as_individual_queries() {
+        let empty = CollisionShape::new_convex_polygon(Vec::<[f32; 2]>::new());
+        assert_same_contacts_as_individual_queries(&empty);
+    }
+
+    fn assert_same_contacts_as_individual_queries(shape: &CollisionShape) {
+        let others: [CollisionShape; 20] = core::array::from_fn(|i| {
+            let i = u8::try_from(i).unwrap();
+            let x = f32::from(i) * 0.25 - 2.5;
+            let y = f32::from(i % 3) - 1.0;
+            CollisionShape::new_circle(0.5).with_transform(Transform::from_translation([x, y]))
+        });
+        let mut expected = others
+            .iter()
+            .enumerate()
+            .filter_map(|(index, other)| Some((index, shape.contact_with(other)?)));
+        for (index, contact) in shape.contacts_with(&others) {
+            let (expected_index, expected_contact) = expected.next().unwrap();
+            assert_eq!(index, expected_index);
+            assert_ulps_eq!(contact.penetration, expected_contact.penetration);
+            assert_ulps_eq!(
+                Vec2::from(contact.normal),
+                Vec2::from(expected_contact.normal)
+            );
+            assert_eq!(contact.features, expected_contact.features);
+        }
+        assert!(expected.next().is_none());
+    }
+}
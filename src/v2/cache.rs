@@ -0,0 +1,172 @@
+use glam::Vec2;
+
+use super::{epa, gjk, minkowski, CollisionShape, Contact, QueryConfig, SupportPoint};
+
+/// Cache of the last query between a pair of shapes
+///
+/// Passing the same cache to consecutive queries between the same pair of shapes (for instance from one frame to the next)
+/// seeds each query with the separating axis (or contact normal) found by the previous one.
+/// When the shapes don't move much between queries, this significantly reduces the number of iterations.
+///
+/// The cache is only a hint: the results are equivalent whether a cache is used or not.
+/// But a cache should not be shared between different pairs of shapes, nor should the order of the shapes be swapped.
+///
+/// The cached queries use the [`QueryConfig`] of the cache (see [`with_config`](Self::with_config)).
+///
+/// # Example
+///
+/// ```
+/// use impacted::{CollisionShape, PairCache, Transform};
+/// let shape1 = CollisionShape::new_circle(1.0);
+/// let mut shape2 = CollisionShape::new_rectangle(2.0, 2.0);
+/// let mut cache = PairCache::new();
+/// for x in [5.0, 4.0, 3.0, 2.0, 1.0] {
+///     shape2.set_transform(Transform::from_translation([x, 0.0]));
+///     let is_collided = shape1.is_collided_with_cached(&shape2, &mut cache);
+///     assert_eq!(is_collided, x <= 2.0);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PairCache {
+    axis: Option<Vec2>,
+    config: QueryConfig,
+}
+
+impl PairCache {
+    /// Create an empty cache
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty cache, for queries using the given `config`
+    #[inline]
+    #[must_use]
+    pub fn with_config(config: QueryConfig) -> Self {
+        Self { axis: None, config }
+    }
+
+    /// Forget the result of the previous query
+    #[inline]
+    pub fn clear(&mut self) {
+        self.axis = None;
+    }
+
+    fn initial_axis(&self, shape1: &CollisionShape, shape2: &CollisionShape) -> Vec2 {
+        self.axis.unwrap_or_else(|| shape1.initial_axis(shape2))
+    }
+
+    fn store(&mut self, axis: Vec2) {
+        if axis != Vec2::ZERO && axis.is_finite() {
+            self.axis = Some(axis);
+        }
+    }
+}
+
+impl CollisionShape {
+    /// Same as [`is_collided_with`](Self::is_collided_with), but seeded by (and updating) the given `cache`
+    ///
+    /// See [`PairCache`]
+    #[must_use]
+    pub fn is_collided_with_cached(&self, other: &Self, cache: &mut PairCache) -> bool {
+        match self.find_simplex_enclosing_origin(
+            other,
+            cache.initial_axis(self, other),
+            cache.config.max_gjk_iterations,
+        ) {
+            Ok(simplex) => {
+                cache.store(epa::closest_edge_normal(simplex));
+                true
+            }
+            Err(not_enclosed) => {
+                cache.store(not_enclosed.direction());
+                false
+            }
+        }
+    }
+
+    /// Same as [`contact_with`](Self::contact_with), but seeded by (and updating) the given `cache`
+    ///
+    /// See [`PairCache`]
+    #[must_use]
+    pub fn contact_with_cached(&self, other: &Self, cache: &mut PairCache) -> Option<Contact> {
+        let difference = minkowski::Difference {
+            shape1: self,
+            shape2: other,
+        };
+        let simplex = match gjk::find_simplex_enclosing_origin(
+            &difference,
+            SupportPoint::from(cache.initial_axis(self, other)),
+            cache.config.max_gjk_iterations,
+        ) {
+            Ok(simplex) => simplex,
+            Err(not_enclosed) => {
+                cache.store(not_enclosed.direction().point);
+                return None;
+            }
+        };
+        let penetration = epa::generate_contact(&difference, simplex, &cache.config);
+        cache.store(-penetration.normal.point);
+        Some(self.contact(other, penetration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::Transform;
+
+    use super::*;
+
+    #[test]
+    fn stores_separating_axis() {
+        let shape1 = CollisionShape::new_circle(1.0);
+        let shape2 =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([3.0, 0.0]));
+        let mut cache = PairCache::new();
+        assert!(!shape1.is_collided_with_cached(&shape2, &mut cache));
+        let axis = cache.axis.unwrap();
+        assert!(axis.x > 0.0, "{axis:?}");
+    }
+
+    #[test]
+    fn stores_contact_normal() {
+        let shape1 = CollisionShape::new_circle(1.0);
+        let shape2 =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([1.5, 0.0]));
+        let mut cache = PairCache::new();
+        let contact = shape1.contact_with_cached(&shape2, &mut cache).unwrap();
+        assert_eq!(cache.axis, Some(-Vec2::from(contact.normal)));
+    }
+
+    #[test]
+    fn stores_axis_on_overlap() {
+        let shape1 = CollisionShape::new_circle(1.0);
+        let shape2 =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([1.5, 0.0]));
+        let mut cache = PairCache::new();
+        assert!(shape1.is_collided_with_cached(&shape2, &mut cache));
+        assert!(cache.axis.is_some());
+        let contact = shape1.contact_with_cached(&shape2, &mut cache).unwrap();
+        assert_abs_diff_eq!(contact.penetration, 0.5, epsilon = 1e-4);
+        assert_abs_diff_eq!(contact.normal[0], -1.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn clear() {
+        let mut cache = PairCache::new();
+        cache.store(Vec2::X);
+        cache.clear();
+        assert_eq!(cache.axis, None);
+    }
+
+    #[test]
+    fn ignores_invalid_axis() {
+        let mut cache = PairCache::new();
+        cache.store(Vec2::ZERO);
+        cache.store(Vec2::NAN);
+        assert_eq!(cache.axis, None);
+    }
+}
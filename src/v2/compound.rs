@@ -0,0 +1,147 @@
+use alloc::vec::Vec;
+
+use glam::Vec2;
+
+use super::{decompose::convex_decomposition, CollisionShape, Contact, QueryConfig};
+
+/// A shape made of several [`CollisionShape`]s
+///
+/// The collision queries only work with convex shapes.
+/// Concave shapes (and shapes with holes) can be represented as a compound of convex parts.
+///
+/// # Example
+///
+/// ```
+/// use impacted::{CollisionShape, CompoundShape, Transform};
+/// // An L shape
+/// let compound = CompoundShape::new([
+///     CollisionShape::new_convex_polygon([[0.0, 0.0], [3.0, 0.0], [3.0, 1.0], [0.0, 1.0]]),
+///     CollisionShape::new_convex_polygon([[0.0, 1.0], [1.0, 1.0], [1.0, 3.0], [0.0, 3.0]]),
+/// ]);
+/// let circle = CollisionShape::new_circle(0.5);
+/// assert!(compound.is_collided_with(&circle.clone().with_transform(Transform::from_translation([0.5, 2.5]))));
+/// assert!(!compound.is_collided_with(&circle.with_transform(Transform::from_translation([2.5, 2.5]))));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompoundShape {
+    shapes: Vec<CollisionShape>,
+}
+
+impl CompoundShape {
+    /// Create a compound shape from its parts
+    #[must_use]
+    pub fn new(shapes: impl IntoIterator<Item = CollisionShape>) -> Self {
+        Self {
+            shapes: shapes.into_iter().collect(),
+        }
+    }
+
+    /// Create a compound shape from a simple polygon, that may be concave
+    ///
+    /// The polygon is split into convex polygons. Its vertices may be in clockwise or counter-clockwise order,
+    /// and it must not intersect itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::CompoundShape;
+    /// let l_shape = CompoundShape::new_polygon([[0.0, 0.0], [3.0, 0.0], [3.0, 1.0], [1.0, 1.0], [1.0, 3.0], [0.0, 3.0]]);
+    /// assert_eq!(l_shape.shapes().len(), 2);
+    /// ```
+    #[must_use]
+    pub fn new_polygon<P: Into<[f32; 2]>>(points: impl IntoIterator<Item = P>) -> Self {
+        let points: Vec<Vec2> = points.into_iter().map(|p| p.into().into()).collect();
+        convex_decomposition(&points)
+            .into_iter()
+            .map(CollisionShape::new_convex_polygon)
+            .collect()
+    }
+
+    /// Returns the parts of the compound shape
+    #[must_use]
+    pub fn shapes(&self) -> &[CollisionShape] {
+        &self.shapes
+    }
+
+    /// Returns true if any part of the compound shape overlaps with `other`
+    #[must_use]
+    pub fn is_collided_with(&self, other: &CollisionShape) -> bool {
+        self.shapes
+            .iter()
+            .any(|shape| shape.is_collided_with(other))
+    }
+
+    /// Returns the deepest contact between the parts of the compound shape and `other`
+    ///
+    /// Returns `None` if no part collides with `other`.
+    #[must_use]
+    pub fn contact_with(&self, other: &CollisionShape) -> Option<Contact> {
+        self.contact_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`contact_with`](Self::contact_with), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn contact_with_config(
+        &self,
+        other: &CollisionShape,
+        config: &QueryConfig,
+    ) -> Option<Contact> {
+        self.shapes
+            .iter()
+            .filter_map(|shape| shape.contact_with_config(other, config))
+            .max_by(|c1, c2| c1.penetration.total_cmp(&c2.penetration))
+    }
+}
+
+impl From<Vec<CollisionShape>> for CompoundShape {
+    fn from(shapes: Vec<CollisionShape>) -> Self {
+        Self { shapes }
+    }
+}
+
+impl FromIterator<CollisionShape> for CompoundShape {
+    fn from_iter<T: IntoIterator<Item = CollisionShape>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl IntoIterator for CompoundShape {
+    type Item = CollisionShape;
+    type IntoIter = alloc::vec::IntoIter<CollisionShape>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.shapes.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::Transform;
+
+    use super::*;
+
+    #[test]
+    fn contact_is_the_deepest() {
+        let compound = CompoundShape::new([
+            CollisionShape::new_rectangle(2.0, 2.0),
+            CollisionShape::new_rectangle(2.0, 2.0)
+                .with_transform(Transform::from_translation([1.5, 0.0])),
+        ]);
+        let circle =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([3.0, 0.0]));
+        let contact = compound.contact_with(&circle).unwrap();
+        assert_abs_diff_eq!(contact.penetration, 0.5, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn empty_compound_never_collides() {
+        let compound = CompoundShape::default();
+        assert!(!compound.is_collided_with(&CollisionShape::new_circle(1.0)));
+        assert!(compound
+            .contact_with(&CollisionShape::new_circle(1.0))
+            .is_none());
+    }
+}
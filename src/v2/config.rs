@@ -0,0 +1,66 @@
+/// Tolerance and iteration limits of the collision queries
+///
+/// Lower limits bound the time spent in a query (even for nearly degenerate inputs), at the cost of accuracy.
+///
+/// # Example
+///
+/// ```
+/// use impacted::{CollisionShape, QueryConfig, Transform};
+/// let config = QueryConfig::default()
+///     .with_epsilon(1e-3)
+///     .with_max_epa_iterations(10);
+/// let shape1 = CollisionShape::new_circle(1.0);
+/// let shape2 = CollisionShape::new_circle(1.0)
+///     .with_transform(Transform::from_scale_angle_translation([1.0, 2.0], 0.0, [1.5, 0.0]));
+/// let contact = shape1.contact_with_config(&shape2, &config).unwrap();
+/// assert!(contact.penetration > 0.4 && contact.penetration < 0.6);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QueryConfig<S = f32> {
+    pub(crate) epsilon: S,
+    pub(crate) max_gjk_iterations: usize,
+    pub(crate) max_epa_iterations: usize,
+}
+
+impl Default for QueryConfig {
+    /// A relative tolerance of `1e-5`, and at most 1000 iterations of both GJK and EPA
+    fn default() -> Self {
+        Self {
+            epsilon: 1e-5,
+            max_gjk_iterations: 1000,
+            max_epa_iterations: 1000,
+        }
+    }
+}
+
+impl<S> QueryConfig<S> {
+    /// Set the relative tolerance under which the contact generation (EPA) stops refining the contact
+    ///
+    /// A larger epsilon makes the contact generation faster but less accurate.
+    #[inline]
+    #[must_use]
+    pub fn with_epsilon(mut self, epsilon: S) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Set the maximum number of iterations of the collision detection (GJK)
+    ///
+    /// If the collision detection doesn't conclude within that many iterations, the shapes are considered as not colliding.
+    #[inline]
+    #[must_use]
+    pub fn with_max_gjk_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_gjk_iterations = max_iterations;
+        self
+    }
+
+    /// Set the maximum number of iterations of the contact generation (EPA)
+    ///
+    /// If the contact generation doesn't converge within that many iterations, the best contact found so far is returned.
+    #[inline]
+    #[must_use]
+    pub fn with_max_epa_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_epa_iterations = max_iterations;
+        self
+    }
+}
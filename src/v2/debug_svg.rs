@@ -0,0 +1,406 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use glam::Vec2;
+
+use super::{
+    gjk::{self, Simplex},
+    minkowski,
+    shapes::ConvexPolygon,
+    CollisionShape, QueryConfig, Support,
+};
+
+const STYLE: &str = "\
+.shape { fill: #3b82f6; fill-opacity: 0.2; stroke: #1d4ed8; }
+.axis-x { stroke: #dc2626; }
+.axis-y { stroke: #16a34a; }
+.normal { stroke: #7c3aed; stroke-dasharray: 4 2; }
+.penetration { stroke: #ea580c; }
+.minkowski { fill: #a3a3a3; fill-opacity: 0.2; stroke: #525252; stroke-dasharray: 2 2; }
+.simplex { fill: #facc15; fill-opacity: 0.4; stroke: #a16207; }
+.origin { stroke: #000000; }
+* { stroke-width: 1.5; vector-effect: non-scaling-stroke; }
+";
+
+/// A scene of collision shapes and contacts, to render as an SVG image for debugging
+///
+/// The image shows each shape, with the x (red) and y (green) axes of its transform,
+/// and for each pair of shapes added with [`add_contact`](Self::add_contact), the contact normal (dashed)
+/// and the penetration vector (orange) starting from the deepest point of the first shape.
+///
+/// The Minkowski difference of the pairs, with the simplex enclosing the origin found by GJK (when the shapes collide),
+/// can be shown with [`with_minkowski_difference`](Self::with_minkowski_difference).
+///
+/// The y axis points up in the image. Each element has a `<title>` naming the shape index or the pair.
+///
+/// # Example
+///
+/// ```
+/// use impacted::{CollisionShape, DebugScene, Transform};
+/// let mut scene = DebugScene::new().with_minkowski_difference(true);
+/// let floor = scene.add_shape(CollisionShape::new_rectangle(10.0, 1.0));
+/// let crate_ = scene.add_shape(
+///     CollisionShape::new_rectangle(1.0, 1.0).with_transform(Transform::from_angle_translation(0.3, [0.0, 0.8])),
+/// );
+/// scene.add_contact(crate_, floor);
+///
+/// let svg = scene.to_svg();
+/// assert!(svg.starts_with("<svg"));
+/// // Write it to a file, or attach it to a bug report
+/// ```
+#[derive(Debug, Clone)]
+pub struct DebugScene {
+    shapes: Vec<CollisionShape>,
+    contacts: Vec<[usize; 2]>,
+    tolerance: f32,
+    vector_length: f32,
+    minkowski_difference: bool,
+    config: QueryConfig,
+}
+
+impl Default for DebugScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DebugScene {
+    /// Create an empty scene
+    ///
+    /// Circles are drawn with a tolerance of `0.01`, and the normals and axes are 1 unit long.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            shapes: Vec::new(),
+            contacts: Vec::new(),
+            tolerance: 0.01,
+            vector_length: 1.0,
+            minkowski_difference: false,
+            config: QueryConfig::default(),
+        }
+    }
+
+    /// Set the maximum distance between the drawn outline of the circles and the actual circles
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the length of the drawn contact normals, and of the unit axes of the transforms
+    #[must_use]
+    pub fn with_vector_length(mut self, length: f32) -> Self {
+        self.vector_length = length;
+        self
+    }
+
+    /// Draw the Minkowski difference of each pair added with [`add_contact`](Self::add_contact),
+    /// and the simplex enclosing the origin found by GJK
+    ///
+    /// The Minkowski difference is approximated by its support points in many directions
+    /// (as many as the vertices of a circle of the same size, see [`with_tolerance`](Self::with_tolerance)).
+    #[must_use]
+    pub fn with_minkowski_difference(mut self, enabled: bool) -> Self {
+        self.minkowski_difference = enabled;
+        self
+    }
+
+    /// Set the configuration of the contact queries (see [`CollisionShape::contact_with_config`])
+    #[must_use]
+    pub fn with_config(mut self, config: QueryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Add a shape to the scene, and returns its index
+    pub fn add_shape(&mut self, shape: CollisionShape) -> usize {
+        self.shapes.push(shape);
+        self.shapes.len() - 1
+    }
+
+    /// Draw the contact between the shapes at the given indices (as returned by [`add_shape`](Self::add_shape))
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds
+    pub fn add_contact(&mut self, shape1: usize, shape2: usize) {
+        assert!(
+            shape1 < self.shapes.len() && shape2 < self.shapes.len(),
+            "shape index out of bounds"
+        );
+        self.contacts.push([shape1, shape2]);
+    }
+
+    /// Returns the SVG image of the scene
+    #[must_use]
+    pub fn to_svg(&self) -> String {
+        let mut svg = Svg::default();
+        for (index, shape) in self.shapes.iter().enumerate() {
+            self.draw_shape(&mut svg, index, shape);
+        }
+        for &[index1, index2] in &self.contacts {
+            let (shape1, shape2) = (&self.shapes[index1], &self.shapes[index2]);
+            let title = Title::Pair(index1, index2);
+            if self.minkowski_difference {
+                self.draw_minkowski_difference(&mut svg, title, shape1, shape2);
+            }
+            if let Some(contact) = shape1.contact_with_config(shape2, &self.config) {
+                let normal = Vec2::from(contact.normal);
+                let anchor = shape1.support(-normal);
+                svg.line(
+                    "normal",
+                    title,
+                    anchor,
+                    anchor + normal * self.vector_length,
+                );
+                svg.arrow(
+                    "penetration",
+                    title,
+                    anchor,
+                    anchor + normal * contact.penetration,
+                );
+            }
+        }
+        svg.finish()
+    }
+
+    fn draw_shape(&self, svg: &mut Svg, index: usize, shape: &CollisionShape) {
+        let title = Title::Shape(index);
+        svg.polygon("shape", title, &shape.world_outline(self.tolerance));
+        let transform = shape.transform().local_to_world();
+        let origin = transform.translation;
+        let [x_axis, y_axis] = [transform.matrix2.x_axis, transform.matrix2.y_axis];
+        svg.arrow(
+            "axis-x",
+            title,
+            origin,
+            origin + x_axis * self.vector_length,
+        );
+        svg.arrow(
+            "axis-y",
+            title,
+            origin,
+            origin + y_axis * self.vector_length,
+        );
+    }
+
+    fn draw_minkowski_difference(
+        &self,
+        svg: &mut Svg,
+        title: Title,
+        shape1: &CollisionShape,
+        shape2: &CollisionShape,
+    ) {
+        let difference = minkowski::Difference { shape1, shape2 };
+        // Sample the support points in the directions of the vertices of a circle as large as the difference,
+        // so that the sampled polygon is as precise as the outline of that circle
+        let extent = [Vec2::X, Vec2::Y, Vec2::NEG_X, Vec2::NEG_Y]
+            .into_iter()
+            .map(|direction| difference.support(direction).length())
+            .fold(0.0, f32::max);
+        let directions = CollisionShape::new_circle(extent).world_outline(self.tolerance);
+        let polygon = ConvexPolygon::new(
+            directions
+                .into_iter()
+                .map(|direction| difference.support(direction)),
+        );
+        svg.polygon("minkowski", title, polygon.vertices_slice());
+        let size = self.vector_length * 0.25;
+        svg.line("origin", title, Vec2::new(-size, 0.0), Vec2::new(size, 0.0));
+        svg.line("origin", title, Vec2::new(0.0, -size), Vec2::new(0.0, size));
+
+        if let Ok(simplex) = gjk::find_simplex_enclosing_origin(
+            &difference,
+            shape1.initial_axis(shape2),
+            self.config.max_gjk_iterations,
+        ) {
+            let points: Vec<Vec2> = match simplex {
+                Simplex::Point(p) => [p].into(),
+                Simplex::Line(p1, p2) => [p1, p2].into(),
+                Simplex::Triangle(p1, p2, p3) => [p1, p2, p3].into(),
+            };
+            svg.polygon("simplex", title, &points);
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Title {
+    Shape(usize),
+    Pair(usize, usize),
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Title::Shape(index) => write!(f, "shape {index}"),
+            Title::Pair(index1, index2) => write!(f, "shapes {index1} and {index2}"),
+        }
+    }
+}
+
+/// SVG elements being written, with the bounds of their points
+struct Svg {
+    body: String,
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self {
+            body: String::new(),
+            min: Vec2::INFINITY,
+            max: Vec2::NEG_INFINITY,
+        }
+    }
+}
+
+impl Svg {
+    fn include(&mut self, point: Vec2) {
+        if point.is_finite() {
+            self.min = self.min.min(point);
+            self.max = self.max.max(point);
+        }
+    }
+
+    /// Writes a polygon (or a polyline if it has less than 3 points)
+    fn polygon(&mut self, class: &str, title: Title, points: &[Vec2]) {
+        let element = if points.len() < 3 {
+            "polyline"
+        } else {
+            "polygon"
+        };
+        let _ = write!(self.body, r#"<{element} class="{class}" points=""#);
+        for (index, &point) in points.iter().enumerate() {
+            self.include(point);
+            let separator = if index == 0 { "" } else { " " };
+            let _ = write!(self.body, "{separator}{},{}", point.x, point.y);
+        }
+        let _ = writeln!(self.body, r#""><title>{title}</title></{element}>"#);
+    }
+
+    fn line(&mut self, class: &str, title: Title, start: Vec2, end: Vec2) {
+        self.polygon(class, title, &[start, end]);
+    }
+
+    /// Writes a line with an arrow head at `end`
+    fn arrow(&mut self, class: &str, title: Title, start: Vec2, end: Vec2) {
+        let head = (start - end) * 0.2;
+        let side = head.perp() * 0.5;
+        self.polygon(class, title, &[end + head + side, end, end + head - side]);
+        self.line(class, title, start, end);
+    }
+
+    fn finish(self) -> String {
+        let (min, max) = if self.min.cmple(self.max).all() {
+            (self.min, self.max)
+        } else {
+            (Vec2::ZERO, Vec2::ZERO)
+        };
+        let margin = ((max - min).max_element() * 0.05).max(0.1);
+        let (min, size) = (min - margin, max - min + 2.0 * margin);
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            min.x,
+            -(min.y + size.y),
+            size.x,
+            size.y
+        );
+        let _ = writeln!(svg, "<style>\n{STYLE}</style>");
+        let _ = writeln!(svg, r#"<g transform="scale(1 -1)" fill="none">"#);
+        svg.push_str(&self.body);
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::Transform;
+
+    use super::*;
+
+    fn scene(minkowski_difference: bool) -> DebugScene {
+        let mut scene = DebugScene::new().with_minkowski_difference(minkowski_difference);
+        let floor = scene.add_shape(CollisionShape::new_rectangle(10.0, 1.0));
+        let ball = scene.add_shape(
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([0.0, 1.0])),
+        );
+        scene.add_contact(ball, floor);
+        scene
+    }
+
+    fn count(svg: &str, pattern: &str) -> usize {
+        svg.matches(pattern).count()
+    }
+
+    #[test]
+    fn draws_shapes_and_contacts() {
+        let svg = scene(false).to_svg();
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox=""#));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(count(&svg, r#"<polygon class="shape""#), 2);
+        assert_eq!(count(&svg, r#"class="axis-x""#), 4);
+        assert_eq!(count(&svg, r#"class="normal""#), 1);
+        assert_eq!(count(&svg, r#"class="penetration""#), 2);
+        assert_eq!(count(&svg, r#"class="minkowski""#), 0);
+        // The ball penetrates by 0.5, from its bottom
+        assert!(svg.contains(r#"<polyline class="penetration" points="0,0 0,0.5">"#));
+        assert!(svg.contains("<title>shapes 1 and 0</title>"));
+    }
+
+    #[test]
+    fn draws_minkowski_difference_and_simplex() {
+        let svg = scene(true).to_svg();
+        assert_eq!(count(&svg, r#"class="minkowski""#), 1);
+        assert_eq!(count(&svg, r#"class="origin""#), 2);
+        assert_eq!(count(&svg, r#"class="simplex""#), 1);
+    }
+
+    #[test]
+    fn minkowski_difference_of_circles_is_a_circle() {
+        let mut scene = DebugScene::new()
+            .with_minkowski_difference(true)
+            .with_tolerance(1e-6);
+        let shape1 = scene.add_shape(CollisionShape::new_circle(1.0));
+        let shape2 = scene.add_shape(
+            CollisionShape::new_circle(2.0).with_transform(Transform::from_translation([1.0, 0.0])),
+        );
+        scene.add_contact(shape1, shape2);
+        let svg = scene.to_svg();
+        let prefix = r#"<polygon class="minkowski" points=""#;
+        let start = svg.find(prefix).unwrap() + prefix.len();
+        let points = &svg[start..start + svg[start..].find('"').unwrap()];
+        assert!(points.split(' ').count() <= 1024);
+        for point in points.split(' ') {
+            let (x, y) = point.split_once(',').unwrap();
+            let point = Vec2::new(x.parse().unwrap(), y.parse().unwrap());
+            assert_abs_diff_eq!(point.distance(Vec2::new(-1.0, 0.0)), 3.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn separated_shapes_have_no_contact_nor_simplex() {
+        let mut scene = DebugScene::new().with_minkowski_difference(true);
+        let shape1 = scene.add_shape(CollisionShape::new_circle(1.0));
+        let shape2 = scene.add_shape(
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([5.0, 0.0])),
+        );
+        scene.add_contact(shape1, shape2);
+        let svg = scene.to_svg();
+        assert_eq!(count(&svg, r#"class="minkowski""#), 1);
+        assert_eq!(count(&svg, r#"class="simplex""#), 0);
+        assert_eq!(count(&svg, r#"class="normal""#), 0);
+    }
+
+    #[test]
+    fn empty_scene() {
+        let svg = DebugScene::default().to_svg();
+        assert!(svg.contains(r#"viewBox="-0.1 -0.1 0.2 0.2""#));
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+
+use glam::Vec2;
+
+/// Splits a simple polygon (that may be concave) into convex polygons
+///
+/// The polygon is triangulated by ear clipping, then adjacent pieces are merged as long as they remain convex
+/// (Hertel-Mehlhorn algorithm), which gives at most four times the minimum number of convex pieces.
+///
+/// The vertices may be in clockwise or counter-clockwise order.
+/// Self-intersecting polygons are not supported: the result is then only an approximation.
+pub(crate) fn convex_decomposition(polygon: &[Vec2]) -> Vec<Vec<Vec2>> {
+    let mut vertices: Vec<Vec2> = Vec::with_capacity(polygon.len());
+    for &point in polygon {
+        if vertices.last() != Some(&point) {
+            vertices.push(point);
+        }
+    }
+    while vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    if signed_area(&vertices) < 0.0 {
+        vertices.reverse();
+    }
+    if vertices.len() <= 3 || is_convex(&vertices) {
+        return [vertices].into();
+    }
+    merge_convex(triangulate(vertices))
+}
+
+/// Twice the signed area of the polygon (positive if counter-clockwise)
+fn signed_area(polygon: &[Vec2]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(p1, p2)| p1.perp_dot(*p2))
+        .sum()
+}
+
+/// Returns true if the counter-clockwise `polygon` has no reflex vertex
+fn is_convex(polygon: &[Vec2]) -> bool {
+    (0..polygon.len()).all(|index| {
+        let [previous, current, next] = neighbors(polygon, index);
+        (current - previous).perp_dot(next - current) >= 0.0
+    })
+}
+
+fn neighbors(polygon: &[Vec2], index: usize) -> [Vec2; 3] {
+    let len = polygon.len();
+    [
+        polygon[(index + len - 1) % len],
+        polygon[index],
+        polygon[(index + 1) % len],
+    ]
+}
+
+/// Ear clipping of a counter-clockwise polygon
+fn triangulate(mut remaining: Vec<Vec2>) -> Vec<Vec<Vec2>> {
+    let mut triangles = Vec::with_capacity(remaining.len() - 2);
+    while remaining.len() > 3 {
+        let Some(ear) = (0..remaining.len()).find(|&index| is_ear(&remaining, index)) else {
+            // Not a simple polygon: keep the rest as is (it is made convex by the shape constructor)
+            break;
+        };
+        triangles.push(neighbors(&remaining, ear).into());
+        remaining.remove(ear);
+    }
+    triangles.push(remaining);
+    triangles
+}
+
+fn is_ear(polygon: &[Vec2], index: usize) -> bool {
+    let [a, b, c] = neighbors(polygon, index);
+    if (b - a).perp_dot(c - b) <= 0.0 {
+        return false;
+    }
+    polygon
+        .iter()
+        .filter(|&&point| point != a && point != b && point != c)
+        .all(|&point| {
+            (b - a).perp_dot(point - a) < 0.0
+                || (c - b).perp_dot(point - b) < 0.0
+                || (a - c).perp_dot(point - c) < 0.0
+        })
+}
+
+/// Merges the pieces sharing an edge, as long as the result is convex
+fn merge_convex(mut pieces: Vec<Vec<Vec2>>) -> Vec<Vec<Vec2>> {
+    let mut index = 0;
+    while index < pieces.len() {
+        let merged = (index + 1..pieces.len())
+            .find_map(|other| Some((other, merge(&pieces[index], &pieces[other])?)));
+        if let Some((other, merged)) = merged {
+            pieces.swap_remove(other);
+            pieces[index] = merged;
+        } else {
+            index += 1;
+        }
+    }
+    pieces
+}
+
+/// Returns the union of two counter-clockwise polygons if they share an edge and the union is convex
+fn merge(piece1: &[Vec2], piece2: &[Vec2]) -> Option<Vec<Vec2>> {
+    let (start1, start2) = (0..piece1.len()).find_map(|i| {
+        let [a, b] = [piece1[i], piece1[(i + 1) % piece1.len()]];
+        (0..piece2.len())
+            .find(|&j| piece2[j] == b && piece2[(j + 1) % piece2.len()] == a)
+            .map(|j| (i, j))
+    })?;
+    // Walk the first piece from the end of the shared edge to its start, then the rest of the second piece
+    let mut merged: Vec<Vec2> = (1..=piece1.len())
+        .map(|offset| piece1[(start1 + offset) % piece1.len()])
+        .collect();
+    merged.extend((2..piece2.len()).map(|offset| piece2[(start2 + offset) % piece2.len()]));
+    is_convex(&merged).then_some(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn polygon(points: &[[f32; 2]]) -> Vec<Vec2> {
+        points.iter().copied().map(Vec2::from).collect()
+    }
+
+    #[rstest]
+    #[case(&[[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [0.0, 1.0]], 1)]
+    #[case(&[[0.0, 1.0], [2.0, 1.0], [2.0, 0.0], [0.0, 0.0]], 1)]
+    #[case(&[[0.0, 0.0], [3.0, 0.0], [3.0, 1.0], [1.0, 1.0], [1.0, 3.0], [0.0, 3.0]], 2)]
+    #[case(&[[0.0, 3.0], [1.0, 3.0], [1.0, 1.0], [3.0, 1.0], [3.0, 0.0], [0.0, 0.0]], 2)]
+    #[case(&[[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [2.0, 1.0], [0.0, 4.0]], 3)]
+    fn decomposes_into_convex_pieces(#[case] points: &[[f32; 2]], #[case] expected_pieces: usize) {
+        let polygon = polygon(points);
+        let pieces = convex_decomposition(&polygon);
+        assert_eq!(pieces.len(), expected_pieces);
+        let area: f32 = pieces.iter().map(|piece| signed_area(piece)).sum();
+        assert_abs_diff_eq!(area, signed_area(&polygon).abs(), epsilon = 1e-5);
+        for piece in &pieces {
+            assert!(is_convex(piece), "{piece:?} is not convex");
+        }
+    }
+
+    #[test]
+    fn closed_polygon_ignores_repeated_point() {
+        let pieces =
+            convex_decomposition(&polygon(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]]));
+        assert_eq!(pieces, [polygon(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]])]);
+    }
+}
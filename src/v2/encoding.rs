@@ -0,0 +1,461 @@
+use alloc::vec::Vec;
+
+use glam::Vec2;
+
+use super::{
+    shapes::{Circle, ConvexPolygon, Rectangle, Segment, ShapeData},
+    CollisionShape, DecodeError, Transform,
+};
+
+/// Precision of the compact binary encoding of shapes and transforms
+///
+/// Every value is rounded to a multiple of its resolution, and encoded as a variable-length integer
+/// (so that small values take fewer bytes).
+/// The decoded values are within half a resolution of the encoded ones:
+///
+/// * The translations, radii, half extents and points are quantized with the [`resolution`](Self::with_resolution).
+/// * The angles (in radians), scales and the axes of skewed transforms are quantized
+///   with the [`linear_resolution`](Self::with_linear_resolution).
+///
+/// The decoding is deterministic, so that every peer decoding the same bytes gets the same shapes.
+/// For the sender to agree with the receivers, it should use the decoded shapes as well.
+///
+/// # Example
+///
+/// ```
+/// use impacted::{CollisionShape, Quantization, Transform};
+/// let quantization = Quantization::default().with_resolution(0.01);
+/// let shape = CollisionShape::new_rectangle(2.0, 1.0)
+///     .with_transform(Transform::from_angle_translation(0.3, [10.0, 2.0]));
+///
+/// let mut buffer = Vec::new();
+/// shape.encode(&quantization, &mut buffer);
+/// assert_eq!(buffer.len(), 11);
+///
+/// let decoded = CollisionShape::decode(&mut buffer.as_slice(), &quantization).unwrap();
+/// assert!(decoded.is_collided_with(&shape));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quantization {
+    resolution: f32,
+    linear_resolution: f32,
+}
+
+impl Default for Quantization {
+    /// A resolution of `1/1024`, and a linear resolution of `1/4096`
+    fn default() -> Self {
+        Self {
+            resolution: 1.0 / 1024.0,
+            linear_resolution: 1.0 / 4096.0,
+        }
+    }
+}
+
+impl Quantization {
+    /// Set the resolution of the positions and dimensions
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resolution is not finite and positive
+    #[inline]
+    #[must_use]
+    pub fn with_resolution(mut self, resolution: f32) -> Self {
+        assert!(
+            resolution.is_finite() && resolution > 0.0,
+            "the resolution must be finite and positive, got {resolution}"
+        );
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set the resolution of the angles (in radians), the scales, and the axes of skewed transforms
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resolution is not finite and positive
+    #[inline]
+    #[must_use]
+    pub fn with_linear_resolution(mut self, resolution: f32) -> Self {
+        assert!(
+            resolution.is_finite() && resolution > 0.0,
+            "the linear resolution must be finite and positive, got {resolution}"
+        );
+        self.linear_resolution = resolution;
+        self
+    }
+}
+
+/// Tags of the encoded transforms
+const TRANSLATION: u8 = 0;
+const ANGLE_TRANSLATION: u8 = 1;
+const SCALE_ANGLE_TRANSLATION: u8 = 2;
+const AFFINE: u8 = 3;
+
+/// Tags of the encoded shapes
+const CIRCLE: u8 = 0;
+const RECTANGLE: u8 = 1;
+const SEGMENT: u8 = 2;
+const CONVEX_POLYGON: u8 = 3;
+
+impl Transform {
+    /// Append the transform to `buffer`, in the compact format described in [`Quantization`]
+    ///
+    /// A translation takes a single byte plus the translation, a rotation adds the angle, and a scale adds two values.
+    /// Skewed transforms store the two axes of the linear part.
+    pub fn encode(&self, quantization: &Quantization, buffer: &mut Vec<u8>) {
+        let linear = quantization.linear_resolution;
+        let matrix = self.local_to_world().matrix2;
+        let (scale, angle) = (self.scale(), self.angle());
+        let rotation_scale = Self::from_scale_angle_translation(scale, angle, [0.0, 0.0]);
+        let tolerance = 1e-5 * matrix.x_axis.length().max(matrix.y_axis.length()).max(1.0);
+        if !rotation_scale
+            .local_to_world()
+            .matrix2
+            .abs_diff_eq(matrix, tolerance)
+        {
+            buffer.push(AFFINE);
+            write_vector(buffer, matrix.x_axis, linear);
+            write_vector(buffer, matrix.y_axis, linear);
+        } else if scale.map(|s| quantize(s, linear)) != [quantize(1.0, linear); 2] {
+            buffer.push(SCALE_ANGLE_TRANSLATION);
+            write_vector(buffer, scale.into(), linear);
+            write_value(buffer, angle, linear);
+        } else if quantize(angle, linear) != 0 {
+            buffer.push(ANGLE_TRANSLATION);
+            write_value(buffer, angle, linear);
+        } else {
+            buffer.push(TRANSLATION);
+        }
+        write_vector(buffer, self.translation().into(), quantization.resolution);
+    }
+
+    /// Read a transform encoded by [`encode`](Self::encode) from the start of `bytes`, and advance `bytes` past it
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if `bytes` doesn't start with a valid transform
+    pub fn decode(bytes: &mut &[u8], quantization: &Quantization) -> Result<Self, DecodeError> {
+        let linear = quantization.linear_resolution;
+        let transform = match read_byte(bytes)? {
+            TRANSLATION => Self::from_translation(read_vector(bytes, quantization.resolution)?),
+            ANGLE_TRANSLATION => {
+                let angle = read_value(bytes, linear)?;
+                Self::from_angle_translation(angle, read_vector(bytes, quantization.resolution)?)
+            }
+            SCALE_ANGLE_TRANSLATION => {
+                let scale = read_vector(bytes, linear)?;
+                let angle = read_value(bytes, linear)?;
+                Self::from_scale_angle_translation(
+                    scale,
+                    angle,
+                    read_vector(bytes, quantization.resolution)?,
+                )
+            }
+            AFFINE => {
+                let x_axis = read_vector(bytes, linear)?;
+                let y_axis = read_vector(bytes, linear)?;
+                Self::from_affine(x_axis, y_axis, read_vector(bytes, quantization.resolution)?)
+            }
+            _ => return Err(DecodeError::Invalid),
+        };
+        Ok(transform)
+    }
+}
+
+impl ShapeData {
+    /// Append the shape to `buffer`, in the compact format described in [`Quantization`]
+    pub fn encode(&self, quantization: &Quantization, buffer: &mut Vec<u8>) {
+        let resolution = quantization.resolution;
+        match self {
+            ShapeData::Circle(circle) => {
+                buffer.push(CIRCLE);
+                write_value(buffer, circle.radius(), resolution);
+            }
+            ShapeData::Rectangle(rect) => {
+                buffer.push(RECTANGLE);
+                write_vector(buffer, rect.half_extents().into(), resolution);
+            }
+            ShapeData::Segment(segment) => {
+                buffer.push(SEGMENT);
+                for point in segment.points() {
+                    write_vector(buffer, point.into(), resolution);
+                }
+            }
+            ShapeData::ConvexPolygon(polygon) => {
+                buffer.push(CONVEX_POLYGON);
+                write_varint(buffer, polygon.vertices().len() as u64);
+                for vertex in polygon.vertices() {
+                    write_vector(buffer, vertex.into(), resolution);
+                }
+            }
+        }
+    }
+
+    /// Read a shape encoded by [`encode`](Self::encode) from the start of `bytes`, and advance `bytes` past it
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if `bytes` doesn't start with a valid shape
+    pub fn decode(bytes: &mut &[u8], quantization: &Quantization) -> Result<Self, DecodeError> {
+        let resolution = quantization.resolution;
+        let shape = match read_byte(bytes)? {
+            CIRCLE => Circle::new(read_non_negative(bytes, resolution)?).into(),
+            RECTANGLE => {
+                let x = read_non_negative(bytes, resolution)?;
+                let y = read_non_negative(bytes, resolution)?;
+                Rectangle::new(x * 2.0, y * 2.0).into()
+            }
+            SEGMENT => {
+                let p1 = read_vector(bytes, resolution)?;
+                Segment::new(p1, read_vector(bytes, resolution)?).into()
+            }
+            CONVEX_POLYGON => {
+                let count = read_varint(bytes)?;
+                // Each vertex takes at least two bytes
+                if count > bytes.len() as u64 / 2 {
+                    return Err(DecodeError::UnexpectedEnd);
+                }
+                let vertices = (0..count)
+                    .map(|_| read_vector(bytes, resolution))
+                    .collect::<Result<Vec<_>, _>>()?;
+                ConvexPolygon::new(vertices).into()
+            }
+            _ => return Err(DecodeError::Invalid),
+        };
+        Ok(shape)
+    }
+}
+
+impl CollisionShape {
+    /// Append the shape and its transform to `buffer`, in the compact format described in [`Quantization`]
+    pub fn encode(&self, quantization: &Quantization, buffer: &mut Vec<u8>) {
+        self.data.encode(quantization, buffer);
+        self.transform.encode(quantization, buffer);
+    }
+
+    /// Read a shape encoded by [`encode`](Self::encode) from the start of `bytes`, and advance `bytes` past it
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if `bytes` doesn't start with a valid shape
+    pub fn decode(bytes: &mut &[u8], quantization: &Quantization) -> Result<Self, DecodeError> {
+        let data = ShapeData::decode(bytes, quantization)?;
+        let transform = Transform::decode(bytes, quantization)?;
+        Ok(Self::from(data).with_transform(transform))
+    }
+}
+
+/// Returns the nearest multiple of `resolution`, as a number of steps (saturating, and zero for NaN)
+#[allow(clippy::cast_possible_truncation)]
+fn quantize(value: f32, resolution: f32) -> i64 {
+    libm::round(f64::from(value) / f64::from(resolution)) as i64
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn write_value(buffer: &mut Vec<u8>, value: f32, resolution: f32) {
+    let steps = quantize(value, resolution);
+    // Zigzag encoding, so that small negative numbers are small as well
+    write_varint(buffer, ((steps << 1) ^ (steps >> 63)) as u64);
+}
+
+fn write_vector(buffer: &mut Vec<u8>, vector: Vec2, resolution: f32) {
+    write_value(buffer, vector.x, resolution);
+    write_value(buffer, vector.y, resolution);
+}
+
+/// LEB128 encoding: 7 bits per byte, the highest bit being set if more bytes follow
+#[allow(clippy::cast_possible_truncation)]
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn read_byte(bytes: &mut &[u8]) -> Result<u8, DecodeError> {
+    let (&first, rest) = bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+    *bytes = rest;
+    Ok(first)
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = read_byte(bytes)?;
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(DecodeError::Invalid)
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss
+)]
+fn read_value(bytes: &mut &[u8], resolution: f32) -> Result<f32, DecodeError> {
+    let zigzag = read_varint(bytes)?;
+    let steps = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+    let value = (steps as f64 * f64::from(resolution)) as f32;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(DecodeError::Invalid)
+    }
+}
+
+/// Same as [`read_value`], but the value must not be negative (like a radius or a half extent)
+fn read_non_negative(bytes: &mut &[u8], resolution: f32) -> Result<f32, DecodeError> {
+    let value = read_value(bytes, resolution)?;
+    if value < 0.0 {
+        Err(DecodeError::Invalid)
+    } else {
+        Ok(value)
+    }
+}
+
+fn read_vector(bytes: &mut &[u8], resolution: f32) -> Result<[f32; 2], DecodeError> {
+    Ok([
+        read_value(bytes, resolution)?,
+        read_value(bytes, resolution)?,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn round_trip(transform: &Transform, quantization: Quantization) -> (Transform, usize) {
+        let mut buffer = Vec::new();
+        transform.encode(&quantization, &mut buffer);
+        let mut bytes = buffer.as_slice();
+        let decoded = Transform::decode(&mut bytes, &quantization).unwrap();
+        assert!(bytes.is_empty());
+        (decoded, buffer.len())
+    }
+
+    #[rstest]
+    #[case(Transform::default(), 3)]
+    #[case(Transform::from_translation([12.3, -4.56]), 6)]
+    #[case(Transform::from_angle_translation(-2.5, [0.0, 0.0]), 6)]
+    #[case(Transform::from_scale_angle_translation([2.0, 0.5], 0.7, [1.0, 2.0]), 12)]
+    #[case(Transform::from_scale_angle_translation([-1.5, 3.0], 0.0, [1.0, 2.0]), 11)]
+    #[case(Transform::from_affine([1.0, 0.0], [0.5, 1.0], [1.0, 2.0]), 12)]
+    fn transform_error_is_bounded(#[case] transform: Transform, #[case] expected_len: usize) {
+        let quantization = Quantization::default();
+        let (decoded, len) = round_trip(&transform, quantization);
+        assert_eq!(len, expected_len);
+        let expected = transform.local_to_world();
+        let actual = decoded.local_to_world();
+        assert!(actual
+            .translation
+            .abs_diff_eq(expected.translation, 0.5 / 1024.0));
+        // The axes are within the linear resolution, times the scale
+        let tolerance = 4.0 / 4096.0;
+        assert!(actual.matrix2.abs_diff_eq(expected.matrix2, tolerance));
+    }
+
+    #[rstest]
+    fn transform_encoding_is_stable(
+        #[values(
+            Transform::from_translation([12.3, -4.56]),
+            Transform::from_angle_translation(-2.5, [0.1, 0.2]),
+            Transform::from_scale_angle_translation([2.0, 0.5], 0.7, [1.0, 2.0]),
+            Transform::from_affine([1.0, 0.2], [0.5, 1.0], [1.0, 2.0]),
+        )]
+        transform: Transform,
+    ) {
+        let quantization = Quantization::default().with_resolution(0.01);
+        let (decoded, _) = round_trip(&transform, quantization);
+        let (decoded_again, _) = round_trip(&decoded, quantization);
+        assert_eq!(decoded_again.local_to_world(), decoded.local_to_world());
+    }
+
+    #[rstest]
+    #[case(Circle::new(1.234).into())]
+    #[case(Rectangle::new(2.5, 0.75).into())]
+    #[case(Segment::new([-1.0, 2.0], [3.3, -4.4]).into())]
+    #[case(ConvexPolygon::new([[0.0, 0.0], [2.0, 0.1], [1.5, 1.5], [-0.3, 1.0]]).into())]
+    fn shape_error_is_bounded(#[case] shape: ShapeData) {
+        let quantization = Quantization::default().with_resolution(0.1);
+        let mut buffer = Vec::new();
+        shape.encode(&quantization, &mut buffer);
+        let mut bytes = buffer.as_slice();
+        let decoded = ShapeData::decode(&mut bytes, &quantization).unwrap();
+        assert!(bytes.is_empty());
+        for direction in [Vec2::X, Vec2::Y, Vec2::NEG_ONE, Vec2::new(1.0, -0.5)] {
+            let [expected, actual] = [&shape, &decoded].map(|s| s.support_feature(direction).0);
+            assert_abs_diff_eq!(actual, expected, epsilon = 0.05);
+        }
+    }
+
+    #[test]
+    fn many_shapes_in_a_buffer() {
+        let quantization = Quantization::default();
+        let shapes = [
+            CollisionShape::new_circle(1.0),
+            CollisionShape::new_rectangle(1.0, 2.0)
+                .with_transform(Transform::from_angle_translation(1.0, [3.0, 4.0])),
+        ];
+        let mut buffer = Vec::new();
+        for shape in &shapes {
+            shape.encode(&quantization, &mut buffer);
+        }
+        let mut bytes = buffer.as_slice();
+        for shape in &shapes {
+            let decoded = CollisionShape::decode(&mut bytes, &quantization).unwrap();
+            assert_abs_diff_eq!(
+                decoded.transform.angle(),
+                shape.transform.angle(),
+                epsilon = 1e-3
+            );
+        }
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn truncated_input() {
+        let mut buffer = Vec::new();
+        CollisionShape::new_rectangle(1.0, 2.0)
+            .with_transform(Transform::from_angle_translation(1.0, [3.0, 4.0]))
+            .encode(&Quantization::default(), &mut buffer);
+        for len in 0..buffer.len() {
+            assert_eq!(
+                CollisionShape::decode(&mut &buffer[..len], &Quantization::default()).unwrap_err(),
+                DecodeError::UnexpectedEnd
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(&[42])]
+    #[case(&[CIRCLE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01])]
+    #[case(&[CIRCLE, 2, 17])]
+    #[case(&[CIRCLE, 1, TRANSLATION, 0, 0])]
+    #[case(&[RECTANGLE, 2, 1, TRANSLATION, 0, 0])]
+    #[case(&[RECTANGLE, 2, 3, TRANSLATION, 0, 0])]
+    fn invalid_input(#[case] bytes: &[u8]) {
+        assert_eq!(
+            CollisionShape::decode(&mut &bytes[..], &Quantization::default()).unwrap_err(),
+            DecodeError::Invalid
+        );
+    }
+
+    #[rstest]
+    #[should_panic = "resolution must be finite and positive"]
+    fn invalid_resolution(#[values(0.0, -0.01, f32::NAN, f32::INFINITY)] resolution: f32) {
+        let _ = Quantization::default().with_resolution(resolution);
+    }
+
+    #[rstest]
+    #[should_panic = "resolution must be finite and positive"]
+    fn invalid_linear_resolution(#[values(0.0, -0.01, f32::NAN)] resolution: f32) {
+        let _ = Quantization::default().with_linear_resolution(resolution);
+    }
+}
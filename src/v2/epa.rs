@@ -1,93 +1,261 @@
 use core::{
     mem,
-    ops::{Neg, Sub},
+    ops::{Add, Mul, Neg, Sub},
 };
 
-use smallvec::{smallvec, SmallVec};
+use smallvec::SmallVec;
 
-use super::{gjk, math::*, Contact, Support};
+use super::{gjk, math::*, QueryConfig, Support};
+
+/// Penetration of the origin in the polytope generated by EPA
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct Penetration<S, V> {
+    /// Direction in which the first shape should be moved to resolve the penetration
+    pub(super) normal: V,
+    pub(super) depth: S,
+    /// End points of the polytope edge on which the penetration was found
+    ///
+    /// These are support points of the difference, and can be used to identify the features of the shapes in contact.
+    pub(super) points: [V; 2],
+    /// False if the maximum number of iterations was reached before the polytope stopped expanding
+    pub(super) converged: bool,
+}
 
 pub(super) fn generate_contact<S, V>(
     difference: &impl Support<V>,
     simplex: gjk::Simplex<V>,
-) -> Contact<S, V>
+    config: &QueryConfig<S>,
+) -> Penetration<S, V>
+where
+    V: Copy
+        + Default
+        + Sub<V, Output = V>
+        + Neg<Output = V>
+        + Dot<Output = S>
+        + Cross<Output = S>
+        + Perp
+        + Normalize,
+    S: Scalar,
+{
+    generate_contact_in(&mut Simplex::new(), difference, simplex, config)
+}
+
+/// Returns the outward normal of the edge of the `simplex` that is the closest to the origin
+///
+/// This is where EPA starts expanding the polytope, and a cheap estimate of the contact normal.
+pub(super) fn closest_edge_normal<V>(simplex: gjk::Simplex<V>) -> V
+where
+    V: Copy + Default + Sub<V, Output = V> + Neg<Output = V> + Dot + Cross + Perp + Normalize,
+    <V as Dot>::Output: PartialOrd + CmpToZero,
+    <V as Cross>::Output: CmpToZero,
+{
+    Simplex::from(simplex).closest_edge().normal
+}
+
+/// Same as [`generate_contact`], but reusing the memory of the given `polytope`
+///
+/// This avoids re-allocating memory when generating many contacts in a row.
+pub(super) fn generate_contact_in<S, V>(
+    polytope: &mut Simplex<V>,
+    difference: &impl Support<V>,
+    simplex: gjk::Simplex<V>,
+    config: &QueryConfig<S>,
+) -> Penetration<S, V>
+where
+    V: Copy
+        + Default
+        + Sub<V, Output = V>
+        + Neg<Output = V>
+        + Dot<Output = S>
+        + Cross<Output = S>
+        + Perp
+        + Normalize,
+    S: Scalar,
+{
+    polytope.reset(simplex);
+    for _ in 0..config.max_epa_iterations {
+        let edge = polytope.closest_edge();
+        let support = difference.support(edge.normal);
+        if !is_expanding(support.dot(edge.normal), edge.distance, config.epsilon) {
+            return polytope.penetration(edge, true);
+        }
+        polytope.insert(edge.index, support);
+    }
+    polytope.penetration(polytope.closest_edge(), false)
+}
+
+/// Same as [`generate_contact`], but only consider the resolutions (movement of the first shape) within the `cone`
+pub(super) fn generate_contact_in_cone<S, V>(
+    difference: &impl Support<V>,
+    simplex: gjk::Simplex<V>,
+    cone: &Cone<V>,
+    config: &QueryConfig<S>,
+) -> Penetration<S, V>
 where
     V: Copy
         + Default
+        + Add<V, Output = V>
         + Sub<V, Output = V>
         + Neg<Output = V>
+        + Mul<S, Output = V>
         + Dot<Output = S>
         + Cross<Output = S>
         + Perp
         + Normalize,
-    S: PartialOrd + Sub<Output = S> + CmpToZero,
+    S: Scalar,
 {
     let mut simplex: Simplex<V> = simplex.into();
-    for _ in 0..1000 {
-        let edge = simplex.closest_edge();
+    let mut closest = None;
+    let mut converged = false;
+    for _ in 0..config.max_epa_iterations {
+        let in_cone = simplex.closest_edge_in_cone(cone);
+        closest = in_cone
+            .as_ref()
+            .map(|(edge, point)| (*point, simplex.edge_points(edge.index)));
+        let edge = in_cone.map_or_else(|| simplex.closest_edge(), |(edge, _)| edge);
         let support = difference.support(edge.normal);
-        let penetration = support.dot(edge.normal);
-        if (penetration - edge.distance).is_negative() {
-            return edge.into();
+        if !is_expanding(support.dot(edge.normal), edge.distance, config.epsilon) {
+            converged = true;
+            break;
         }
         simplex.insert(edge.index, support);
     }
-    simplex.closest_edge().into()
+    if let Some((point, direction, (p1, p2))) =
+        closest.and_then(|(point, points)| Some((point, point.normalize()?, points)))
+    {
+        Penetration {
+            normal: -direction,
+            depth: point.dot(direction),
+            points: [p1, p2],
+            converged,
+        }
+    } else {
+        let (p1, p2) = simplex.edge_points(simplex.closest_edge().index);
+        Penetration {
+            normal: -cone.axis,
+            depth: S::ZERO,
+            points: [p1, p2],
+            converged,
+        }
+    }
 }
 
-struct Edge<V: Dot> {
-    index: usize,
-    normal: V,
-    distance: <V as Dot>::Output,
+/// Returns true if a support point at `penetration` in the direction of an edge at `distance`
+/// would significantly expand the polytope.
+///
+/// A relative tolerance (`epsilon`) is necessary to not insert points almost identical to existing ones,
+/// that would form edges too short to have a meaningful normal.
+fn is_expanding<S: Scalar>(penetration: S, distance: S, epsilon: S) -> bool {
+    penetration - distance > penetration * epsilon
+}
+
+/// Set of directions, between `right` and `left` (counter-clockwise), and not further than 90 degrees from `axis`
+pub(super) struct Cone<V> {
+    pub(super) axis: V,
+    pub(super) left: V,
+    pub(super) right: V,
 }
 
-impl<V, S> From<Edge<V>> for Contact<S, V>
+impl<V> Cone<V>
 where
-    V: Neg<Output = V> + Dot<Output = S>,
+    V: Copy
+        + Add<V, Output = V>
+        + Sub<V, Output = V>
+        + Mul<<V as Dot>::Output, Output = V>
+        + Dot
+        + Cross<Output = <V as Dot>::Output>,
+    <V as Dot>::Output: Scalar,
 {
-    fn from(edge: Edge<V>) -> Self {
-        Contact {
-            normal: -edge.normal,
-            penetration: edge.distance,
+    /// Returns the point closest to the origin on the part of the segment `p1`-`p2` that is inside the cone
+    fn closest_point_on_segment(&self, p1: V, p2: V) -> Option<V> {
+        let mut range = (<V as Dot>::Output::ZERO, <V as Dot>::Output::ONE);
+        clip(&mut range, self.right.cross(p1), self.right.cross(p2))?;
+        clip(&mut range, p1.cross(self.left), p2.cross(self.left))?;
+        clip(&mut range, self.axis.dot(p1), self.axis.dot(p2))?;
+        let (min, max) = range;
+        if min > max {
+            return None;
         }
+        let edge = p2 - p1;
+        let length_squared = edge.dot(edge);
+        let foot = if length_squared.is_positive() {
+            (-p1.dot(edge) / length_squared).max(min).min(max)
+        } else {
+            min
+        };
+        Some(p1 + edge * foot)
+    }
+}
+
+/// Restrict the `range` of parameters `t` for which `f1 + t * (f2 - f1)` is positive
+fn clip<S: Scalar>(range: &mut (S, S), f1: S, f2: S) -> Option<()> {
+    match (f1.is_negative(), f2.is_negative()) {
+        (true, true) => return None,
+        (true, false) => range.0 = range.0.max(f1 / (f1 - f2)),
+        (false, true) => range.1 = range.1.min(f1 / (f1 - f2)),
+        (false, false) => (),
     }
+    Some(())
+}
+
+struct Edge<V: Dot> {
+    index: usize,
+    normal: V,
+    distance: <V as Dot>::Output,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct Simplex<V> {
+pub(super) struct Simplex<V> {
     points: SmallVec<[V; 10]>,
 }
 
+impl<V> Simplex<V> {
+    pub(super) fn new() -> Self {
+        Self {
+            points: SmallVec::new(),
+        }
+    }
+}
+
 impl<V> Simplex<V>
 where
-    V: Dot + Copy + Sub<V, Output = V> + Perp + Normalize + Default,
-    <V as Dot>::Output: PartialOrd,
+    V: Dot + Copy + Sub<V, Output = V> + Neg<Output = V> + Perp + Normalize + Default,
+    <V as Dot>::Output: PartialOrd + CmpToZero,
 {
+    /// Returns the edge the closest to the origin (the first one in case of a tie or NaN distances)
+    ///
+    /// The polytope cannot be empty here: it is always reset from a GJK simplex (that has at least one point)
+    /// before being expanded, and expanding only inserts points.
     fn closest_edge(&self) -> Edge<V> {
-        (0..self.points.len())
+        (1..self.points.len())
             .map(|index| self.edge(index))
-            .min_by(|e1, e2| {
-                e1.distance
-                    .partial_cmp(&e2.distance)
-                    .unwrap_or(core::cmp::Ordering::Equal)
+            .fold(self.edge(0), |closest, edge| {
+                if edge.distance < closest.distance {
+                    edge
+                } else {
+                    closest
+                }
             })
-            .expect("no edge in epa simplex")
     }
 
     fn edge(&self, index: usize) -> Edge<V> {
-        let p1 = self.points[index];
-        let p2 = self
-            .points
-            .get(index + 1)
-            .copied()
-            .unwrap_or_else(|| self.points[0]);
+        let (p1, p2) = self.edge_points(index);
         let edge = p2 - p1;
-        let normal = edge
+        let mut normal = edge
             .perp()
             .normalize()
             .or_else(|| p1.normalize())
             .unwrap_or_default();
-        let distance = p1.dot(normal);
+        // The polytope encloses the origin, so the outward normal is the one at a positive distance.
+        // If the origin is exactly on the edge, fall back to the side of the rest of the polytope.
+        let mut distance = p1.dot(normal);
+        let opposite = self.points[(index + 2) % self.points.len()];
+        if distance.is_negative()
+            || (distance.is_zero() && (opposite - p1).dot(normal).is_positive())
+        {
+            normal = -normal;
+            distance = p1.dot(normal);
+        }
         Edge {
             index,
             normal,
@@ -96,37 +264,105 @@ where
     }
 }
 
+impl<V> Simplex<V>
+where
+    V: Copy
+        + Default
+        + Add<V, Output = V>
+        + Sub<V, Output = V>
+        + Neg<Output = V>
+        + Mul<<V as Dot>::Output, Output = V>
+        + Dot
+        + Cross<Output = <V as Dot>::Output>
+        + Perp
+        + Normalize,
+    <V as Dot>::Output: Scalar,
+{
+    /// Returns the edge having the point the closest to the origin inside the cone, and that point
+    fn closest_edge_in_cone(&self, cone: &Cone<V>) -> Option<(Edge<V>, V)> {
+        (0..self.points.len())
+            .filter_map(|index| {
+                let (p1, p2) = self.edge_points(index);
+                let point = cone.closest_point_on_segment(p1, p2)?;
+                Some((self.edge(index), point))
+            })
+            .min_by(|(_, p1), (_, p2)| {
+                p1.magnitude_squared()
+                    .partial_cmp(&p2.magnitude_squared())
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })
+    }
+}
+
+impl<V: Copy> Simplex<V> {
+    fn penetration(&self, edge: Edge<V>, converged: bool) -> Penetration<<V as Dot>::Output, V>
+    where
+        V: Dot + Neg<Output = V>,
+    {
+        let (p1, p2) = self.edge_points(edge.index);
+        Penetration {
+            normal: -edge.normal,
+            depth: edge.distance,
+            points: [p1, p2],
+            converged,
+        }
+    }
+
+    fn edge_points(&self, index: usize) -> (V, V) {
+        let p1 = self.points[index];
+        let p2 = self
+            .points
+            .get(index + 1)
+            .copied()
+            .unwrap_or_else(|| self.points[0]);
+        (p1, p2)
+    }
+}
+
 impl<V> Simplex<V> {
     fn insert(&mut self, index: usize, point: V) {
         self.points.insert(index + 1, point);
     }
 }
 
-impl<V> From<gjk::Simplex<V>> for Simplex<V>
+impl<V> Simplex<V>
 where
     V: Copy + Sub<V, Output = V> + Cross,
     <V as Cross>::Output: CmpToZero,
 {
-    fn from(simplex: gjk::Simplex<V>) -> Self {
-        Self {
-            points: match simplex {
-                gjk::Simplex::Point(p) => smallvec![p],
-                gjk::Simplex::Line(p1, p2) => smallvec![p1, p2],
-                gjk::Simplex::Triangle(p1, mut p2, mut p3) => {
-                    if (p2 - p1).cross(p3 - p2).is_negative() {
-                        mem::swap(&mut p2, &mut p3);
-                    }
-                    smallvec![p1, p2, p3]
+    /// Replace the points by the ones of the `simplex`, keeping the allocated memory
+    fn reset(&mut self, simplex: gjk::Simplex<V>) {
+        self.points.clear();
+        match simplex {
+            gjk::Simplex::Point(p) => self.points.push(p),
+            gjk::Simplex::Line(p1, p2) => self.points.extend([p1, p2]),
+            gjk::Simplex::Triangle(p1, mut p2, mut p3) => {
+                if (p2 - p1).cross(p3 - p2).is_negative() {
+                    mem::swap(&mut p2, &mut p3);
                 }
-            },
+                self.points.extend([p1, p2, p3]);
+            }
         }
     }
 }
 
+impl<V> From<gjk::Simplex<V>> for Simplex<V>
+where
+    V: Copy + Sub<V, Output = V> + Cross,
+    <V as Cross>::Output: CmpToZero,
+{
+    fn from(simplex: gjk::Simplex<V>) -> Self {
+        let mut result = Self::new();
+        result.reset(simplex);
+        result
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use approx::assert_ulps_eq;
     use glam::Vec2;
+    use smallvec::smallvec;
 
     use super::*;
 
@@ -163,6 +399,36 @@ mod tests {
             assert_eq!(normal, -Vec2::Y);
         }
 
+        #[test]
+        fn edge_normals_point_outward() {
+            let simplex: Simplex<Vec2> = gjk::Simplex::Triangle(
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(0.0, 1.0),
+            )
+            .into();
+            for index in 0..3 {
+                let Edge {
+                    normal, distance, ..
+                } = simplex.edge(index);
+                assert!(distance > 0.0, "{normal:?} {distance}");
+            }
+            let Edge {
+                normal, distance, ..
+            } = simplex.closest_edge();
+            assert!(normal.x > 0.0 && normal.y > 0.0, "{normal:?}");
+            assert_ulps_eq!(distance, 1.0 / 5f32.sqrt());
+        }
+
+        #[test]
+        fn reset_replaces_all_points() {
+            let mut simplex: Simplex<Vec2> =
+                gjk::Simplex::Triangle(Vec2::ZERO, Vec2::X, Vec2::Y).into();
+            simplex.insert(0, Vec2::new(0.5, -1.0));
+            simplex.reset(gjk::Simplex::Line(-Vec2::X, Vec2::X));
+            assert_eq!(&simplex.points[..], &[-Vec2::X, Vec2::X]);
+        }
+
         #[test]
         fn insert_point() {
             let mut simplex = Simplex {
@@ -0,0 +1,340 @@
+use core::fmt;
+
+use glam::Vec2;
+
+use super::{
+    analytic::ClosedForm,
+    epa, gjk, minkowski,
+    shapes::{Circle, ConvexPolygon, Rectangle, Segment, ShapeData},
+    CollisionShape, Contact, QueryConfig, SupportPoint, Transform,
+};
+
+/// Error returned by the fallible queries
+///
+/// See [`CollisionShape::try_contact_with`]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// A shape or transform has a non-finite value (NaN or infinite), or such a value was produced during the query
+    NonFinite,
+    /// A shape is degenerate, like a circle with a negative radius
+    Degenerate,
+    /// The query did not converge within the iteration limits (see [`QueryConfig`])
+    NotConverged,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::NonFinite => f.write_str("non-finite value in collision query"),
+            QueryError::Degenerate => f.write_str("degenerate shape in collision query"),
+            QueryError::NotConverged => {
+                f.write_str("collision query did not converge within the iteration limits")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QueryError {}
+
+/// Error returned by the fallible [`Transform`] constructors
+///
+/// See [`Transform::try_from_scale_angle_translation`]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransformError {
+    /// The scale, angle or translation is not finite (NaN or infinite)
+    NonFinite,
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformError::NonFinite => f.write_str("non-finite value in transform"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransformError {}
+
+/// Error returned when decoding a shape or a transform
+///
+/// See [`CollisionShape::decode`]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input ended before the end of the shape or transform
+    UnexpectedEnd,
+    /// The input is not a shape or transform encoded by this crate
+    Invalid,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => f.write_str("unexpected end of encoded shape"),
+            DecodeError::Invalid => f.write_str("invalid encoded shape"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+impl CollisionShape {
+    /// Same as [`is_collided_with`](Self::is_collided_with), but returns an error instead of `false`
+    /// if the shapes are invalid or if the collision detection doesn't converge
+    ///
+    /// # Errors
+    ///
+    /// See [`QueryError`]
+    pub fn try_is_collided_with(&self, other: &Self) -> Result<bool, QueryError> {
+        self.try_is_collided_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`try_is_collided_with`](Self::try_is_collided_with), with the given tolerance and iteration limits
+    ///
+    /// # Errors
+    ///
+    /// See [`QueryError`]
+    pub fn try_is_collided_with_config(
+        &self,
+        other: &Self,
+        config: &QueryConfig,
+    ) -> Result<bool, QueryError> {
+        self.validate()?;
+        other.validate()?;
+        match self.closed_form_contact(other) {
+            ClosedForm::Separated => return Ok(false),
+            ClosedForm::Contact(_) => return Ok(true),
+            ClosedForm::Unsupported => (),
+        }
+        let difference = minkowski::Difference {
+            shape1: self,
+            shape2: other,
+        };
+        match gjk::find_simplex_enclosing_origin(
+            &difference,
+            self.initial_axis(other),
+            config.max_gjk_iterations,
+        ) {
+            Ok(_) => Ok(true),
+            Err(not_enclosed) => not_enclosed_error(&not_enclosed).map(|()| false),
+        }
+    }
+
+    /// Same as [`contact_with`](Self::contact_with), but returns an error instead of an arbitrary result
+    /// if the shapes are invalid or if the collision detection or contact generation doesn't converge
+    ///
+    /// # Errors
+    ///
+    /// See [`QueryError`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::{CollisionShape, QueryError};
+    /// let shape1 = CollisionShape::new_circle(1.0);
+    /// let shape2 = CollisionShape::new_circle(f32::NAN);
+    /// assert_eq!(shape1.try_contact_with(&shape2), Err(QueryError::NonFinite));
+    /// ```
+    pub fn try_contact_with(&self, other: &Self) -> Result<Option<Contact>, QueryError> {
+        self.try_contact_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`try_contact_with`](Self::try_contact_with), with the given tolerance and iteration limits
+    ///
+    /// # Errors
+    ///
+    /// See [`QueryError`]
+    pub fn try_contact_with_config(
+        &self,
+        other: &Self,
+        config: &QueryConfig,
+    ) -> Result<Option<Contact>, QueryError> {
+        self.validate()?;
+        other.validate()?;
+        let contact = match self.closed_form_contact(other) {
+            ClosedForm::Separated => return Ok(None),
+            ClosedForm::Contact(contact) => contact,
+            ClosedForm::Unsupported => {
+                let difference = minkowski::Difference {
+                    shape1: self,
+                    shape2: other,
+                };
+                let simplex = match gjk::find_simplex_enclosing_origin(
+                    &difference,
+                    SupportPoint::from(self.initial_axis(other)),
+                    config.max_gjk_iterations,
+                ) {
+                    Ok(simplex) => simplex,
+                    Err(not_enclosed) => return not_enclosed_error(&not_enclosed).map(|()| None),
+                };
+                let penetration = epa::generate_contact(&difference, simplex, config);
+                if !penetration.converged {
+                    return Err(QueryError::NotConverged);
+                }
+                self.contact(other, penetration)
+            }
+        };
+        if contact.penetration.is_finite() && Vec2::from(contact.normal).is_finite() {
+            Ok(Some(contact))
+        } else {
+            Err(QueryError::NonFinite)
+        }
+    }
+
+    fn validate(&self) -> Result<(), QueryError> {
+        self.transform.validate()?;
+        self.data.validate()
+    }
+}
+
+/// Returns `Ok` if the origin was proven outside the shape, and the error otherwise
+fn not_enclosed_error<V>(not_enclosed: &gjk::NotEnclosed<V>) -> Result<(), QueryError> {
+    match not_enclosed {
+        gjk::NotEnclosed::Separated(_) => Ok(()),
+        gjk::NotEnclosed::Invalid(_) => Err(QueryError::NonFinite),
+        gjk::NotEnclosed::NotConverged(_) => Err(QueryError::NotConverged),
+    }
+}
+
+impl Transform {
+    fn validate(&self) -> Result<(), QueryError> {
+        if self.local_to_world().is_finite() {
+            Ok(())
+        } else {
+            Err(QueryError::NonFinite)
+        }
+    }
+}
+
+impl Circle {
+    fn validate(self) -> Result<(), QueryError> {
+        if !self.radius().is_finite() {
+            Err(QueryError::NonFinite)
+        } else if self.radius() < 0.0 {
+            Err(QueryError::Degenerate)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Rectangle {
+    fn validate(self) -> Result<(), QueryError> {
+        if Vec2::from(self.half_extents()).is_finite() {
+            Ok(())
+        } else {
+            Err(QueryError::NonFinite)
+        }
+    }
+}
+
+impl Segment {
+    fn validate(&self) -> Result<(), QueryError> {
+        if self.points().into_iter().all(|p| Vec2::from(p).is_finite()) {
+            Ok(())
+        } else {
+            Err(QueryError::NonFinite)
+        }
+    }
+}
+
+impl ConvexPolygon {
+    fn validate(&self) -> Result<(), QueryError> {
+        if !self.vertices_slice().iter().all(|v| v.is_finite()) {
+            Err(QueryError::NonFinite)
+        } else if self.vertices_slice().is_empty() {
+            Err(QueryError::Degenerate)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl ShapeData {
+    fn validate(&self) -> Result<(), QueryError> {
+        match self {
+            ShapeData::Circle(circle) => circle.validate(),
+            ShapeData::Rectangle(rect) => rect.validate(),
+            ShapeData::Segment(segment) => segment.validate(),
+            ShapeData::ConvexPolygon(polygon) => polygon.validate(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(CollisionShape::new_circle(f32::NAN), QueryError::NonFinite)]
+    #[case(CollisionShape::new_circle(-1.0), QueryError::Degenerate)]
+    #[case(
+        CollisionShape::new_rectangle(f32::INFINITY, 1.0),
+        QueryError::NonFinite
+    )]
+    #[case(CollisionShape::new_segment([0.0, f32::NAN], [1.0, 0.0]), QueryError::NonFinite)]
+    #[case(
+        CollisionShape::new_rectangle(1.0, 1.0).with_transform(Transform::from_translation([f32::NAN, 0.0])),
+        QueryError::NonFinite
+    )]
+    fn invalid_shape(#[case] invalid: CollisionShape, #[case] expected: QueryError) {
+        let valid = CollisionShape::new_rectangle(2.0, 2.0);
+        assert_eq!(invalid.try_is_collided_with(&valid), Err(expected));
+        assert_eq!(valid.try_is_collided_with(&invalid), Err(expected));
+        assert_eq!(invalid.try_contact_with(&valid), Err(expected));
+        assert_eq!(valid.try_contact_with(&invalid), Err(expected));
+    }
+
+    #[test]
+    fn valid_shapes() {
+        let shape1 = CollisionShape::new_rectangle(2.0, 2.0);
+        let shape2 = CollisionShape::new_segment([0.5, -2.0], [0.0, 2.0]);
+        let shape3 = CollisionShape::new_segment([3.0, -2.0], [3.0, 2.0]);
+        assert_eq!(shape1.try_is_collided_with(&shape2), Ok(true));
+        assert_eq!(
+            shape1.try_contact_with(&shape2),
+            Ok(shape1.contact_with(&shape2))
+        );
+        assert_eq!(shape1.try_is_collided_with(&shape3), Ok(false));
+        assert_eq!(shape1.try_contact_with(&shape3), Ok(None));
+    }
+
+    #[test]
+    fn zero_scale_shapes_are_valid() {
+        let shape = CollisionShape::new_rectangle(2.0, 2.0);
+        let point = CollisionShape::new_circle(1.0).with_transform(
+            Transform::from_scale_angle_translation([0.0, 0.0], 0.0, [0.5, 0.0]),
+        );
+        let far_point = CollisionShape::new_circle(1.0).with_transform(
+            Transform::from_scale_angle_translation([0.0, 0.0], 0.0, [1.5, 0.0]),
+        );
+        assert_eq!(shape.try_is_collided_with(&point), Ok(true));
+        let contact = shape.try_contact_with(&point).unwrap().unwrap();
+        assert!((contact.penetration - 0.5).abs() < 1e-4);
+        assert_eq!(shape.try_is_collided_with(&far_point), Ok(false));
+        assert_eq!(shape.try_contact_with(&far_point), Ok(None));
+    }
+
+    #[test]
+    fn not_converged() {
+        let shape1 = CollisionShape::new_rectangle(2.0, 2.0);
+        let shape2 = CollisionShape::new_segment([0.5, -2.0], [0.0, 2.0]);
+        let config = QueryConfig::default().with_max_gjk_iterations(0);
+        assert_eq!(
+            shape1.try_is_collided_with_config(&shape2, &config),
+            Err(QueryError::NotConverged)
+        );
+        let config = QueryConfig::default().with_max_epa_iterations(0);
+        assert_eq!(
+            shape1.try_contact_with_config(&shape2, &config),
+            Err(QueryError::NotConverged)
+        );
+    }
+}
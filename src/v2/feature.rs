@@ -0,0 +1,350 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use glam::Vec2;
+
+use super::{
+    epa,
+    math::*,
+    minkowski,
+    shapes::{Circle, ConvexPolygon, Rectangle, Segment, ShapeData},
+    CollisionShape, Contact, Support,
+};
+
+/// Identifier of a geometric feature (vertex or edge) of a shape
+///
+/// The identifiers are stable: the same feature of a shape always has the same identifier,
+/// whatever the transform of the shape.
+/// They are meant to match contacts across frames (for instance to warm-start a solver).
+///
+/// * The vertices of a [`Rectangle`] are numbered counter-clockwise from the bottom-left corner (in local space),
+///   and the edge `n` goes from the vertex `n` to the next one.
+/// * The vertices of a [`Segment`] are its two [`points`](Segment::points), joined by the edge `0`.
+/// * The vertices of a [`ConvexPolygon`] are numbered in the order of [`ConvexPolygon::vertices`],
+///   and the edge `n` goes from the vertex `n` to the next one.
+///   (The vertices after the 255th all share the identifier `255`.)
+/// * A [`Circle`] has no vertex nor edge, its only feature is [`Feature::Surface`].
+///
+/// See [`Contact::features`]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Feature {
+    /// A vertex, identified by its index in the shape
+    Vertex(u8),
+    /// An edge, identified by the index of its first vertex
+    Edge(u8),
+    /// The smooth boundary of a round shape
+    Surface,
+}
+
+impl Feature {
+    /// Returns the vertex of a rectangle in the quadrant of the given (local) direction
+    pub(crate) fn rectangle_vertex(direction: Vec2) -> Self {
+        Self::quadrant_vertex(direction.x < 0.0, direction.y < 0.0)
+    }
+
+    /// Returns the vertex of a rectangle in the quadrant of the given signs of the (local) coordinates
+    pub(crate) fn quadrant_vertex(negative_x: bool, negative_y: bool) -> Self {
+        Self::Vertex(match (negative_x, negative_y) {
+            (true, true) => 0,
+            (false, true) => 1,
+            (false, false) => 2,
+            (true, false) => 3,
+        })
+    }
+
+    /// Returns the edge of a rectangle facing the given (local) axis-aligned direction
+    pub(crate) fn rectangle_edge(normal: Vec2) -> Self {
+        Self::Edge(if normal.x.abs() > normal.y.abs() {
+            if normal.x < 0.0 {
+                3
+            } else {
+                1
+            }
+        } else if normal.y < 0.0 {
+            0
+        } else {
+            2
+        })
+    }
+
+    /// Returns the smallest feature containing both `f1` and `f2`, on a shape with `vertex_count` vertices
+    ///
+    /// If there is none (the features are not adjacent) `f1` is returned.
+    pub(crate) fn between(f1: Self, f2: Self, vertex_count: u8) -> Self {
+        match (f1, f2, vertex_count) {
+            (f1, f2, _) if f1 == f2 => f1,
+            (Feature::Vertex(_), Feature::Vertex(_), 2) => Feature::Edge(0),
+            (Feature::Vertex(v1), Feature::Vertex(v2), count) if count > 2 => {
+                let next = |vertex: u8| (u16::from(vertex) + 1) % u16::from(count);
+                if u16::from(v2) == next(v1) {
+                    Feature::Edge(v1)
+                } else if u16::from(v1) == next(v2) {
+                    Feature::Edge(v2)
+                } else {
+                    f1
+                }
+            }
+            _ => f1,
+        }
+    }
+}
+
+/// A vector of the minkowski difference, tagged with the features of each shape that produced it
+///
+/// Only support points have features. Vectors derived from them (like directions) have none.
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct SupportPoint<V = Vec2> {
+    pub(crate) point: V,
+    features: Option<[Feature; 2]>,
+}
+
+impl<V> SupportPoint<V> {
+    pub(crate) fn new(point: V, features: [Feature; 2]) -> Self {
+        Self {
+            point,
+            features: Some(features),
+        }
+    }
+
+    /// Returns the features of each shape on the polytope edge between `points`,
+    /// given the number of vertices of each shape
+    pub(crate) fn edge_features(points: &[Self; 2], vertex_counts: [u8; 2]) -> [Feature; 2] {
+        let [p1, p2] = points;
+        let [a1, b1] = p1.features.or(p2.features).unwrap_or([Feature::Surface; 2]);
+        let [a2, b2] = p2.features.unwrap_or([a1, b1]);
+        [
+            Feature::between(a1, a2, vertex_counts[0]),
+            Feature::between(b1, b2, vertex_counts[1]),
+        ]
+    }
+}
+
+impl<V> From<V> for SupportPoint<V> {
+    fn from(point: V) -> Self {
+        Self {
+            point,
+            features: None,
+        }
+    }
+}
+
+impl<V: PartialEq> PartialEq for SupportPoint<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl Support<SupportPoint> for minkowski::Difference<'_, CollisionShape, CollisionShape> {
+    fn support(&self, direction: SupportPoint) -> SupportPoint {
+        let (p1, f1) = self.shape1.support_feature(direction.point);
+        let (p2, f2) = self.shape2.support_feature(-direction.point);
+        SupportPoint::new(p1 - p2, [f1, f2])
+    }
+}
+
+impl CollisionShape {
+    /// Returns the contact data for the `penetration` of `other` into this shape
+    pub(super) fn contact(
+        &self,
+        other: &Self,
+        penetration: epa::Penetration<f32, SupportPoint>,
+    ) -> Contact {
+        Contact {
+            normal: penetration.normal.point.into(),
+            penetration: penetration.depth,
+            features: SupportPoint::edge_features(
+                &penetration.points,
+                [self.data.vertex_count(), other.data.vertex_count()],
+            ),
+        }
+    }
+}
+
+impl Circle {
+    fn support_feature(self, direction: Vec2) -> (Vec2, Feature) {
+        (self.support(direction), Feature::Surface)
+    }
+}
+
+impl Rectangle {
+    fn support_feature(self, direction: Vec2) -> (Vec2, Feature) {
+        (
+            self.support(direction),
+            Feature::rectangle_vertex(direction),
+        )
+    }
+}
+
+impl Segment {
+    fn support_feature(&self, direction: Vec2) -> (Vec2, Feature) {
+        let [p1, p2] = self.points();
+        if Vec2::from(p1).dot(direction) > Vec2::from(p2).dot(direction) {
+            (p1.into(), Feature::Vertex(0))
+        } else {
+            (p2.into(), Feature::Vertex(1))
+        }
+    }
+}
+
+impl ConvexPolygon {
+    fn support_feature(&self, direction: Vec2) -> (Vec2, Feature) {
+        let index = self.support_index(direction);
+        (
+            self.support(direction),
+            Feature::Vertex(u8::try_from(index).unwrap_or(u8::MAX)),
+        )
+    }
+}
+
+impl ShapeData {
+    /// Returns the farthest point of the shape in the given direction, and the feature it belongs to
+    pub(crate) fn support_feature(&self, direction: Vec2) -> (Vec2, Feature) {
+        match self {
+            ShapeData::Circle(circle) => circle.support_feature(direction),
+            ShapeData::Rectangle(rect) => rect.support_feature(direction),
+            ShapeData::Segment(segment) => segment.support_feature(direction),
+            ShapeData::ConvexPolygon(polygon) => polygon.support_feature(direction),
+        }
+    }
+
+    fn vertex_count(&self) -> u8 {
+        match self {
+            ShapeData::Circle(_) => 0,
+            ShapeData::Rectangle(_) => 4,
+            ShapeData::Segment(_) => 2,
+            ShapeData::ConvexPolygon(polygon) => {
+                u8::try_from(polygon.vertices_slice().len()).unwrap_or(u8::MAX)
+            }
+        }
+    }
+}
+
+impl<V: Dot> Dot for SupportPoint<V> {
+    type Output = V::Output;
+    fn dot(self, other: Self) -> Self::Output {
+        self.point.dot(other.point)
+    }
+}
+
+impl<V: Cross> Cross for SupportPoint<V> {
+    type Output = V::Output;
+    fn cross(self, other: Self) -> Self::Output {
+        self.point.cross(other.point)
+    }
+}
+
+impl<V: Perp> Perp for SupportPoint<V> {
+    fn perp(self) -> Self {
+        self.point.perp().into()
+    }
+}
+
+impl<V: Normalize> Normalize for SupportPoint<V> {
+    fn normalize(self) -> Option<Self> {
+        self.point.normalize().map(Into::into)
+    }
+}
+
+impl<V: Add<Output = V>> Add for SupportPoint<V> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        (self.point + rhs.point).into()
+    }
+}
+
+impl<V: Sub<Output = V>> Sub for SupportPoint<V> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        (self.point - rhs.point).into()
+    }
+}
+
+impl<V: Neg<Output = V>> Neg for SupportPoint<V> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        (-self.point).into()
+    }
+}
+
+impl<S, V: Mul<S, Output = V>> Mul<S> for SupportPoint<V> {
+    type Output = Self;
+    fn mul(self, rhs: S) -> Self::Output {
+        (self.point * rhs).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::Transform;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Vec2::new(-1.0, -1.0), Vec2::new(-1.0, -2.0), 0)]
+    #[case(Vec2::new(1.0, -1.0), Vec2::new(1.0, -2.0), 1)]
+    #[case(Vec2::new(1.0, 1.0), Vec2::new(1.0, 2.0), 2)]
+    #[case(Vec2::new(-1.0, 1.0), Vec2::new(-1.0, 2.0), 3)]
+    fn rectangle_vertices(#[case] direction: Vec2, #[case] point: Vec2, #[case] index: u8) {
+        assert_eq!(
+            Rectangle::new(2.0, 4.0).support_feature(direction),
+            (point, Feature::Vertex(index))
+        );
+    }
+
+    #[test]
+    fn rectangle_edges() {
+        let edges =
+            (0..4).map(|i| Feature::between(Feature::Vertex(i), Feature::Vertex((i + 1) % 4), 4));
+        assert!(edges.eq((0..4).map(Feature::Edge)));
+        assert_eq!(
+            Feature::between(Feature::Vertex(0), Feature::Vertex(3), 4),
+            Feature::Edge(3)
+        );
+        assert_eq!(
+            Feature::between(Feature::Vertex(0), Feature::Vertex(2), 4),
+            Feature::Vertex(0)
+        );
+        assert_eq!(
+            Feature::between(Feature::Vertex(1), Feature::Vertex(0), 2),
+            Feature::Edge(0)
+        );
+    }
+
+    #[test]
+    fn box_resting_on_ground_touches_bottom_edge_and_top_edge() {
+        let ground = CollisionShape::new_rectangle(10.0, 2.0)
+            .with_transform(Transform::from_translation([0.0, -1.0]));
+        let block = CollisionShape::new_rectangle(1.0, 1.0)
+            .with_transform(Transform::from_translation([0.3, 0.45]));
+        let contact = block.contact_with(&ground).unwrap();
+        assert_eq!(contact.features, [Feature::Edge(0), Feature::Edge(2)]);
+    }
+
+    #[test]
+    fn features_are_stable_when_shapes_move_slightly() {
+        let ground = CollisionShape::new_rectangle(10.0, 2.0)
+            .with_transform(Transform::from_translation([0.0, -1.0]));
+        let features = [0.0, 0.01, 0.02, 0.05].map(|x| {
+            let block = CollisionShape::new_rectangle(1.0, 1.0)
+                .with_transform(Transform::from_angle_translation(0.2, [x, 0.5]));
+            block.contact_with(&ground).unwrap().features
+        });
+        assert!(features.iter().all(|f| *f == features[0]), "{features:?}");
+        assert!(
+            matches!(features[0], [Feature::Vertex(_), Feature::Edge(2)]),
+            "{features:?}"
+        );
+    }
+
+    #[test]
+    fn circle_features() {
+        let segment = CollisionShape::new_segment([-1.0, 0.0], [1.0, 0.0]);
+        let circle =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([0.0, 0.5]));
+        let contact = circle.contact_with(&segment).unwrap();
+        assert_eq!(contact.features, [Feature::Surface, Feature::Edge(0)]);
+    }
+}
@@ -0,0 +1,260 @@
+use core::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use sealed::sealed;
+
+use super::{__seal_real, Real};
+
+/// Number of fractional bits of [`Fixed`]
+const FRACTIONAL_BITS: u32 = 32;
+
+/// Fixed-point number with 32 integer bits and 32 fractional bits
+///
+/// All operations are implemented with integer arithmetic, so that the results are bit-identical on every platform.
+/// This makes [`FixedCollisionShape`](crate::FixedCollisionShape) suitable for lockstep and rollback networking.
+///
+/// The resolution is `2^-32` (about `2.3e-10`) and the range about `±2.1e9`.
+/// Operations saturate instead of overflowing, and a division by zero saturates as well.
+/// The queries multiply coordinates together, so the distances between the shapes of a query
+/// should stay below about `40000` units.
+///
+/// # Example
+///
+/// ```
+/// use impacted::generic::Fixed;
+/// let x = Fixed::from(3) / Fixed::from(4);
+/// assert_eq!(x, Fixed::from_f64(0.75));
+/// assert_eq!(x.to_bits(), 3 << 30);
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// Zero
+    pub const ZERO: Self = Self(0);
+    /// One
+    pub const ONE: Self = Self(1 << FRACTIONAL_BITS);
+    /// π
+    pub const PI: Self = Self(0x3_243F_6A89);
+    /// π/2
+    const FRAC_PI_2: Self = Self(0x1_921F_B544);
+    /// 2π
+    const TAU: Self = Self(0x6_487E_D511);
+
+    /// Create a number from its raw representation (the value multiplied by `2^32`)
+    #[inline]
+    #[must_use]
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw representation of the number (the value multiplied by `2^32`)
+    #[inline]
+    #[must_use]
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the nearest fixed-point number, saturating if out of range (and zero for NaN)
+    ///
+    /// The conversion is exact-rounded, and therefore deterministic.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_f64(value: f64) -> Self {
+        // `as` saturates, and maps NaN to zero
+        Self(libm::round(value * f64::from(1_u32 << 16) * f64::from(1_u32 << 16)) as i64)
+    }
+
+    /// Returns the nearest `f64` value
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / f64::from(1_u32 << 16) / f64::from(1_u32 << 16)
+    }
+
+    fn saturate(value: i128) -> Self {
+        Self(i64::try_from(value).unwrap_or(if value < 0 { i64::MIN } else { i64::MAX }))
+    }
+
+    /// Returns the remainder of the euclidean division by `divisor`
+    fn rem_euclid(self, divisor: Self) -> Self {
+        Self(self.0.rem_euclid(divisor.0))
+    }
+
+    /// Sine and cosine of an angle between -π/4 and π/4 (Taylor series)
+    fn sin_cos_octant(self) -> (Self, Self) {
+        let square = self * self;
+        let mut sin = Self::ZERO;
+        let mut cos = Self::ZERO;
+        // Horner evaluation of `x - x³/3! + x⁵/5! - ...` and `1 - x²/2! + x⁴/4! - ...` up to the 14th order
+        for n in (1..=7_i32).rev() {
+            sin = Self::ONE - square * sin / Self::from((2 * n) * (2 * n + 1));
+            cos = Self::ONE - square * cos / Self::from((2 * n - 1) * (2 * n));
+        }
+        (self * sin, cos)
+    }
+}
+
+impl From<i32> for Fixed {
+    #[inline]
+    fn from(value: i32) -> Self {
+        Self(i64::from(value) << FRACTIONAL_BITS)
+    }
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::saturate((i128::from(self.0) * i128::from(rhs.0)) >> FRACTIONAL_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        match rhs.0 {
+            0 if self.0 < 0 => Self(i64::MIN),
+            0 if self.0 > 0 => Self(i64::MAX),
+            0 => Self::ZERO,
+            _ => Self::saturate((i128::from(self.0) << FRACTIONAL_BITS) / i128::from(rhs.0)),
+        }
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(self.0.saturating_neg())
+    }
+}
+
+#[sealed]
+impl Real for Fixed {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const HALF: Self = Self(1 << (FRACTIONAL_BITS - 1));
+    /// About `1e-6`
+    const EPSILON: Self = Self(1 << (FRACTIONAL_BITS - 20));
+
+    /// Square root rounded down, zero for negative numbers
+    fn sqrt(self) -> Self {
+        let Ok(value) = u128::try_from(self.0) else {
+            return Self::ZERO;
+        };
+        // `sqrt(bits / 2^32) * 2^32 == sqrt(bits * 2^32)`
+        let radicand = value << FRACTIONAL_BITS;
+        let mut root = 0_u128;
+        let mut bit = 1_u128 << 47;
+        while bit > 0 {
+            if radicand >= (root | bit) * (root | bit) {
+                root |= bit;
+            }
+            bit >>= 1;
+        }
+        Self::saturate(i128::try_from(root).unwrap_or(i128::MAX))
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        // Reduce to an angle between -π and π, then to an octant around one of the four axes
+        let angle = (self + Self::PI).rem_euclid(Self::TAU) - Self::PI;
+        let quadrant =
+            ((angle + Self::FRAC_PI_2 * Self::HALF) / Self::FRAC_PI_2).0 >> FRACTIONAL_BITS;
+        let quadrant_start = Self::FRAC_PI_2 * Self::from(i32::try_from(quadrant).unwrap_or(0));
+        let (sin, cos) = (angle - quadrant_start).sin_cos_octant();
+        match quadrant.rem_euclid(4) {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+
+    fn is_finite(self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(1.5, 2.25)]
+    #[case(-3.0, 0.125)]
+    #[case(1000.0, -0.001)]
+    #[case(0.0, 7.0)]
+    fn arithmetic(#[case] a: f64, #[case] b: f64) {
+        let (x, y) = (Fixed::from_f64(a), Fixed::from_f64(b));
+        let (a, b) = (x.to_f64(), y.to_f64());
+        assert_abs_diff_eq!((x + y).to_f64(), a + b, epsilon = 1e-9);
+        assert_abs_diff_eq!((x - y).to_f64(), a - b, epsilon = 1e-9);
+        assert_abs_diff_eq!((x * y).to_f64(), a * b, epsilon = 1e-6);
+        assert_abs_diff_eq!((x / y).to_f64(), a / b, epsilon = 1e-6);
+        assert_abs_diff_eq!((-x).to_f64(), -a);
+    }
+
+    #[test]
+    fn saturation() {
+        let big = Fixed::from(i32::MAX);
+        assert_eq!(big * big, Fixed::from_bits(i64::MAX));
+        assert_eq!(-big * big, Fixed::from_bits(i64::MIN));
+        assert_eq!(Fixed::ONE / Fixed::ZERO, Fixed::from_bits(i64::MAX));
+        assert_eq!(Fixed::ZERO / Fixed::ZERO, Fixed::ZERO);
+        assert_eq!(Fixed::from_f64(f64::NAN), Fixed::ZERO);
+        assert_eq!(Fixed::from_f64(1e300), Fixed::from_bits(i64::MAX));
+    }
+
+    #[rstest]
+    fn sqrt(#[values(0.0, 1e-9, 0.25, 2.0, 12345.678, 2e9)] value: f64) {
+        let value = Fixed::from_f64(value);
+        assert_abs_diff_eq!(value.sqrt().to_f64(), value.to_f64().sqrt(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn sqrt_of_negative_is_zero() {
+        assert_eq!(Fixed::from(-4).sqrt(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn sin_cos() {
+        for step in -100..=100 {
+            let angle = f64::from(step) * 0.0731;
+            let (sin, cos) = Fixed::from_f64(angle).sin_cos();
+            assert_abs_diff_eq!(sin.to_f64(), angle.sin(), epsilon = 1e-8);
+            assert_abs_diff_eq!(cos.to_f64(), angle.cos(), epsilon = 1e-8);
+        }
+    }
+}
@@ -0,0 +1,523 @@
+//! Collision shapes generic over the scalar type
+//!
+//! The main [`CollisionShape`](crate::CollisionShape) of this crate uses `f32`,
+//! which loses precision far from the origin (about a centimeter at 100 km).
+//! The shapes of this module can use `f64` instead, or the deterministic [`Fixed`] point numbers,
+//! at the cost of fewer features: only collision test, contact and separation queries are available,
+//! and only for circles, rectangles and segments (there is no convex polygon, nor compound shape).
+//!
+//! [`DCollisionShape`](crate::DCollisionShape) and [`DTransform`](crate::DTransform) are aliases for the `f64` flavor,
+//! [`FixedCollisionShape`](crate::FixedCollisionShape) and [`FixedTransform`](crate::FixedTransform)
+//! for the fixed-point flavor.
+//!
+//! # Example
+//!
+//! ```
+//! # use approx::assert_ulps_eq;
+//! use impacted::{DCollisionShape, DTransform};
+//! let far_away = [1e7, -3e6];
+//! let circle = DCollisionShape::new_circle(1.0)
+//!     .with_transform(DTransform::from_translation(far_away));
+//! let rect = DCollisionShape::new_rectangle(4.0, 4.0)
+//!     .with_transform(DTransform::from_translation([far_away[0] + 2.99, far_away[1]]));
+//! let contact = circle.contact_with(&rect).unwrap();
+//! assert_ulps_eq!(contact.penetration, 0.01, epsilon = 1e-9);
+//! ```
+
+use core::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use sealed::sealed;
+
+use super::{
+    epa, gjk,
+    math::{Dot, Normalize, Vector},
+    minkowski, Contact, Feature, QueryConfig, Separation, Support, SupportPoint,
+};
+
+mod fixed;
+mod transform;
+
+pub use fixed::Fixed;
+pub use transform::Transform;
+
+/// Scalar type that can be used by the shapes of this module
+///
+/// This trait is sealed, and implemented for `f64` and [`Fixed`].
+#[sealed]
+pub trait Real:
+    Copy
+    + Debug
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    #[doc(hidden)]
+    const ZERO: Self;
+    #[doc(hidden)]
+    const ONE: Self;
+    #[doc(hidden)]
+    const HALF: Self;
+    /// Default relative tolerance of the contact generation (see [`QueryConfig::with_epsilon`])
+    #[doc(hidden)]
+    const EPSILON: Self;
+    #[doc(hidden)]
+    #[must_use]
+    fn sqrt(self) -> Self;
+    /// Returns the sine and cosine of the angle (in radians)
+    #[doc(hidden)]
+    fn sin_cos(self) -> (Self, Self);
+    #[doc(hidden)]
+    fn is_finite(self) -> bool;
+}
+
+#[sealed]
+impl Real for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const HALF: Self = 0.5;
+    const EPSILON: Self = 1e-10;
+
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        libm::sincos(self)
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+impl<S: Real> Default for QueryConfig<S> {
+    /// A relative tolerance suited to the precision of `S` (`1e-10` for `f64`),
+    /// and at most 1000 iterations of both GJK and EPA
+    fn default() -> Self {
+        Self {
+            epsilon: S::EPSILON,
+            max_gjk_iterations: 1000,
+            max_epa_iterations: 1000,
+        }
+    }
+}
+
+/// A collision shape, generic over the scalar type `S`
+///
+/// This is the equivalent of [`crate::CollisionShape`], limited to circles, rectangles and segments.
+/// See the [module](self) documentation.
+#[derive(Debug, Clone)]
+pub struct CollisionShape<S> {
+    transform: Transform<S>,
+    data: ShapeData<S>,
+}
+
+#[derive(Debug, Clone)]
+enum ShapeData<S> {
+    Circle { radius: S },
+    Rectangle { half_extents: Vector<S> },
+    Segment { points: [Vector<S>; 2] },
+}
+
+impl<S: Real> CollisionShape<S> {
+    fn new(data: ShapeData<S>) -> Self {
+        Self {
+            transform: Transform::default(),
+            data,
+        }
+    }
+
+    /// Create a circle from its radius
+    ///
+    /// The origin is in the center of the circle
+    #[inline]
+    #[must_use]
+    pub fn new_circle(radius: S) -> Self {
+        Self::new(ShapeData::Circle { radius })
+    }
+
+    /// Create a rectangle from its width and height
+    ///
+    /// The origin is in the center of the rectangle
+    #[inline]
+    #[must_use]
+    pub fn new_rectangle(width: S, height: S) -> Self {
+        let abs = |value: S| if value < S::ZERO { -value } else { value };
+        Self::new(ShapeData::Rectangle {
+            half_extents: Vector::new(abs(width * S::HALF), abs(height * S::HALF)),
+        })
+    }
+
+    /// Create a segment from two points
+    #[inline]
+    #[must_use]
+    pub fn new_segment(p1: impl Into<[S; 2]>, p2: impl Into<[S; 2]>) -> Self {
+        Self::new(ShapeData::Segment {
+            points: [p1.into().into(), p2.into().into()],
+        })
+    }
+
+    /// Set the transform (translation, rotation and scale)
+    ///
+    /// This is equivalent to [`set_transform`](Self::set_transform), but in a builder style,
+    /// useful to set the transform directly at creation
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: impl Into<Transform<S>>) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Set the transform (translation, rotation and scale)
+    #[inline]
+    pub fn set_transform(&mut self, transform: impl Into<Transform<S>>) {
+        self.transform = transform.into();
+    }
+
+    /// Returns true if the two convex shapes geometries are overlapping
+    #[must_use]
+    pub fn is_collided_with(&self, other: &Self) -> bool {
+        self.is_collided_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`is_collided_with`](Self::is_collided_with), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn is_collided_with_config(&self, other: &Self, config: &QueryConfig<S>) -> bool {
+        let difference = minkowski::Difference {
+            shape1: self,
+            shape2: other,
+        };
+        gjk::find_simplex_enclosing_origin(
+            &difference,
+            self.initial_axis(other),
+            config.max_gjk_iterations,
+        )
+        .is_ok()
+    }
+
+    /// Returns contact data with the other shape if they collide. Returns `None` if they don't collide.
+    ///
+    /// The normal of the contact data is pointing toward this shape.
+    /// In other words, if this shape is moved by `contact.normal * contact.penetration`
+    /// the two shapes will no longer be inter-penetrating.
+    #[must_use]
+    pub fn contact_with(&self, other: &Self) -> Option<Contact<S>> {
+        self.contact_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`contact_with`](Self::contact_with), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn contact_with_config(&self, other: &Self, config: &QueryConfig<S>) -> Option<Contact<S>> {
+        let difference = minkowski::Difference {
+            shape1: self,
+            shape2: other,
+        };
+        let simplex = gjk::find_simplex_enclosing_origin(
+            &difference,
+            SupportPoint::from(self.initial_axis(other)),
+            config.max_gjk_iterations,
+        )
+        .ok()?;
+        let penetration = epa::generate_contact(&difference, simplex, config);
+        Some(Contact {
+            normal: penetration.normal.point.into(),
+            penetration: penetration.depth,
+            features: SupportPoint::edge_features(
+                &penetration.points,
+                [self.data.vertex_count(), other.data.vertex_count()],
+            ),
+        })
+    }
+
+    /// Returns the signed distance between the two shapes, and the axis along which they are separated
+    ///
+    /// The distance is positive if the shapes are apart, and negative if they are inter-penetrating.
+    ///
+    /// The normal is pointing toward this shape. In other words, moving this shape along the normal increases the distance.
+    #[must_use]
+    pub fn separation(&self, other: &Self) -> Separation<S> {
+        self.separation_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`separation`](Self::separation), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn separation_with_config(&self, other: &Self, config: &QueryConfig<S>) -> Separation<S> {
+        let difference = minkowski::Difference {
+            shape1: self,
+            shape2: other,
+        };
+        match gjk::find_closest_point_to_origin(
+            &difference,
+            self.initial_axis(other),
+            config.max_gjk_iterations,
+        ) {
+            Ok(closest) => Separation {
+                distance: closest.dot(closest).sqrt(),
+                normal: closest.normalize().unwrap_or_default().into(),
+            },
+            Err(simplex) => {
+                let epa::Penetration { normal, depth, .. } =
+                    epa::generate_contact(&difference, simplex, config);
+                Separation {
+                    distance: -depth,
+                    normal: normal.into(),
+                }
+            }
+        }
+    }
+
+    /// See [`crate::CollisionShape`] (concentric shapes start from the x axis)
+    fn initial_axis(&self, other: &Self) -> Vector<S> {
+        let offset = other.transform.position() - self.transform.position();
+        if offset == Vector::default() {
+            Vector::new(S::ONE, S::ZERO)
+        } else {
+            offset
+        }
+    }
+
+    fn support_feature(&self, direction: Vector<S>) -> (Vector<S>, Feature) {
+        let local_direction = self.transform.direction_to_local(direction);
+        let (local_support, feature) = self.data.support_feature(local_direction);
+        (self.transform.transform_vector(local_support), feature)
+    }
+}
+
+impl<S: Real> ShapeData<S> {
+    /// Returns the farthest point of the shape in the given (local) direction, and the feature it belongs to
+    fn support_feature(&self, direction: Vector<S>) -> (Vector<S>, Feature) {
+        match self {
+            ShapeData::Circle { radius } => (
+                direction
+                    .normalize()
+                    .map_or(Vector::new(*radius, S::ZERO), |d| d * *radius),
+                Feature::Surface,
+            ),
+            ShapeData::Rectangle { half_extents } => {
+                let (negative_x, negative_y) = (direction.x < S::ZERO, direction.y < S::ZERO);
+                (
+                    Vector::new(
+                        if negative_x {
+                            -half_extents.x
+                        } else {
+                            half_extents.x
+                        },
+                        if negative_y {
+                            -half_extents.y
+                        } else {
+                            half_extents.y
+                        },
+                    ),
+                    Feature::quadrant_vertex(negative_x, negative_y),
+                )
+            }
+            ShapeData::Segment { points: [p1, p2] } => {
+                if p1.dot(direction) > p2.dot(direction) {
+                    (*p1, Feature::Vertex(0))
+                } else {
+                    (*p2, Feature::Vertex(1))
+                }
+            }
+        }
+    }
+
+    fn vertex_count(&self) -> u8 {
+        match self {
+            ShapeData::Circle { .. } => 0,
+            ShapeData::Rectangle { .. } => 4,
+            ShapeData::Segment { .. } => 2,
+        }
+    }
+}
+
+impl<S: Real> Support<Vector<S>> for CollisionShape<S> {
+    fn support(&self, direction: Vector<S>) -> Vector<S> {
+        self.support_feature(direction).0
+    }
+}
+
+impl<S: Real> Support<SupportPoint<Vector<S>>>
+    for minkowski::Difference<'_, CollisionShape<S>, CollisionShape<S>>
+{
+    fn support(&self, direction: SupportPoint<Vector<S>>) -> SupportPoint<Vector<S>> {
+        let (p1, f1) = self.shape1.support_feature(direction.point);
+        let (p2, f2) = self.shape2.support_feature(-direction.point);
+        SupportPoint::new(p1 - p2, [f1, f2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use rstest::rstest;
+
+    use super::Fixed;
+    use crate::{
+        shapes::ShapeData as F32ShapeData, CollisionShape as F32CollisionShape, DCollisionShape,
+        DTransform, FixedCollisionShape, FixedTransform, Transform as F32Transform,
+    };
+
+    /// Returns the `f64` equivalent of the `f32` shape, or `None` if the generic shapes don't support it
+    ///
+    /// The match is exhaustive on purpose: a shape added to the `f32` flavor fails to compile here,
+    /// until it is either supported by the generic shapes, or documented as unsupported (in the module documentation).
+    fn generic_equivalent(shape: &F32CollisionShape) -> Option<DCollisionShape> {
+        match shape.shape_data() {
+            F32ShapeData::Circle(circle) => {
+                Some(DCollisionShape::new_circle(f64::from(circle.radius())))
+            }
+            F32ShapeData::Rectangle(rect) => {
+                let [x, y] = rect.half_extents().map(f64::from);
+                Some(DCollisionShape::new_rectangle(x * 2.0, y * 2.0))
+            }
+            F32ShapeData::Segment(segment) => {
+                let [p1, p2] = segment.points().map(|point| point.map(f64::from));
+                Some(DCollisionShape::new_segment(p1, p2))
+            }
+            F32ShapeData::ConvexPolygon(_) => None,
+        }
+    }
+
+    #[test]
+    fn convex_polygons_are_not_supported() {
+        let triangle = F32CollisionShape::new_convex_polygon([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]);
+        assert!(generic_equivalent(&triangle).is_none());
+    }
+
+    #[rstest]
+    #[case([0.0, 0.0], 0.0)]
+    #[case([1.5, 0.3], 0.0)]
+    #[case([2.5, 1.0], 0.7)]
+    #[case([0.5, 3.2], 0.0)]
+    #[case([3.5, 0.0], 0.3)]
+    fn equivalent_to_f32(#[case] position: [f32; 2], #[case] angle: f32) {
+        let pairs = [
+            F32CollisionShape::new_circle(1.0),
+            F32CollisionShape::new_rectangle(2.0, 1.0),
+            F32CollisionShape::new_segment([-1.0, 0.5], [1.0, -0.5]),
+        ]
+        .map(|shape| {
+            let dshape = generic_equivalent(&shape).unwrap();
+            (shape, dshape)
+        });
+        let transform = F32Transform::from_scale_angle_translation([1.5, 0.5], angle, position);
+        let dtransform = DTransform::from_scale_angle_translation(
+            [1.5, 0.5],
+            f64::from(angle),
+            position.map(f64::from),
+        );
+        for (shape1, dshape1) in &pairs {
+            for (shape2, dshape2) in &pairs {
+                let shape2 = shape2.clone().with_transform(transform.clone());
+                let dshape2 = dshape2.clone().with_transform(dtransform.clone());
+                assert_eq!(
+                    shape1.is_collided_with(&shape2),
+                    dshape1.is_collided_with(&dshape2)
+                );
+                let separation = shape1.separation(&shape2);
+                let dseparation = dshape1.separation(&dshape2);
+                assert_abs_diff_eq!(
+                    f64::from(separation.distance),
+                    dseparation.distance,
+                    epsilon = 1e-4
+                );
+                if let (Some(contact), Some(dcontact)) =
+                    (shape1.contact_with(&shape2), dshape1.contact_with(&dshape2))
+                {
+                    assert_abs_diff_eq!(
+                        f64::from(contact.penetration),
+                        dcontact.penetration,
+                        epsilon = 1e-4
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn precision_far_from_origin() {
+        let offset = 1e8;
+        let shape1 = DCollisionShape::new_rectangle(1.0, 1.0)
+            .with_transform(DTransform::from_translation([offset, offset]));
+        let shape2 = DCollisionShape::new_rectangle(1.0, 1.0)
+            .with_transform(DTransform::from_translation([offset + 0.999, offset]));
+        let contact = shape1.contact_with(&shape2).unwrap();
+        assert_abs_diff_eq!(contact.penetration, 0.001, epsilon = 1e-6);
+        assert_abs_diff_eq!(contact.normal[0], -1.0, epsilon = 1e-9);
+        let shape2 = shape2.with_transform(DTransform::from_translation([offset + 1.001, offset]));
+        assert!(!shape1.is_collided_with(&shape2));
+        assert_abs_diff_eq!(shape1.separation(&shape2).distance, 0.001, epsilon = 1e-6);
+    }
+
+    fn fixed_shapes(angle: f64, position: [f64; 2]) -> [(FixedCollisionShape, DCollisionShape); 2] {
+        let transform = FixedTransform::from_scale_angle_translation(
+            [Fixed::from(2), Fixed::ONE],
+            Fixed::from_f64(angle),
+            position.map(Fixed::from_f64),
+        );
+        let dtransform = DTransform::from_scale_angle_translation([2.0, 1.0], angle, position);
+        [
+            (
+                FixedCollisionShape::new_rectangle(Fixed::from(2), Fixed::from(1)),
+                DCollisionShape::new_rectangle(2.0, 1.0),
+            ),
+            (
+                FixedCollisionShape::new_circle(Fixed::ONE).with_transform(transform),
+                DCollisionShape::new_circle(1.0).with_transform(dtransform),
+            ),
+        ]
+    }
+
+    #[rstest]
+    fn fixed_point_equivalent_to_f64(
+        #[values(0.0, 0.4, -2.0)] angle: f64,
+        #[values([0.5, 0.5], [-2.5, 1.0], [1.0, -3.0], [10.0, 0.0])] position: [f64; 2],
+    ) {
+        let [(shape1, dshape1), (shape2, dshape2)] = fixed_shapes(angle, position);
+        assert_eq!(
+            shape1.is_collided_with(&shape2),
+            dshape1.is_collided_with(&dshape2)
+        );
+        assert_abs_diff_eq!(
+            shape1.separation(&shape2).distance.to_f64(),
+            dshape1.separation(&dshape2).distance,
+            epsilon = 1e-5
+        );
+        if let Some(contact) = shape1.contact_with(&shape2) {
+            let dcontact = dshape1.contact_with(&dshape2).unwrap();
+            assert_abs_diff_eq!(
+                contact.penetration.to_f64(),
+                dcontact.penetration,
+                epsilon = 1e-4
+            );
+            assert_abs_diff_eq!(
+                contact.normal[0].to_f64(),
+                dcontact.normal[0],
+                epsilon = 0.01
+            );
+            assert_eq!(contact.features, dcontact.features);
+        }
+    }
+
+    /// The results of fixed-point queries are identical on every platform
+    #[test]
+    fn fixed_point_results_are_bit_exact() {
+        let [(shape1, _), (shape2, _)] = fixed_shapes(0.4, [0.5, 0.5]);
+        let contact = shape1.contact_with(&shape2).unwrap();
+        assert_eq!(
+            [
+                contact.penetration.to_bits(),
+                contact.normal[0].to_bits(),
+                contact.normal[1].to_bits()
+            ],
+            [5_180_627_251, 413, -4_294_967_296]
+        );
+    }
+}
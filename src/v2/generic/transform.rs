@@ -0,0 +1,144 @@
+use crate::v2::math::{Dot, Vector};
+
+use super::Real;
+
+/// Transform that can be used for a [`CollisionShape`](super::CollisionShape), generic over the scalar type `S`
+///
+/// This is the equivalent of [`crate::Transform`]
+#[derive(Debug, Clone)]
+pub struct Transform<S> {
+    x_axis: Vector<S>,
+    y_axis: Vector<S>,
+    translation: Vector<S>,
+}
+
+impl<S: Real> Transform<S> {
+    /// Create a translation transform
+    #[inline]
+    #[must_use]
+    pub fn from_translation(translation: impl Into<[S; 2]>) -> Self {
+        Self {
+            translation: translation.into().into(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a translation and rotation transform
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use impacted::DTransform;
+    /// use core::f64::consts;
+    /// let transform = DTransform::from_angle_translation(consts::FRAC_PI_4, [1.0, 2.0]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_angle_translation(angle: S, translation: impl Into<[S; 2]>) -> Self {
+        Self::from_scale_angle_translation([S::ONE, S::ONE], angle, translation)
+    }
+
+    /// Create a translation, rotation and scale transform
+    ///
+    /// A component of the scale may be zero, in which case the shape collapses to a segment
+    /// (or to a point if both components are zero).
+    #[inline]
+    #[must_use]
+    pub fn from_scale_angle_translation(
+        scale: impl Into<[S; 2]>,
+        angle: S,
+        translation: impl Into<[S; 2]>,
+    ) -> Self {
+        let [scale_x, scale_y] = scale.into();
+        let (sin, cos) = angle.sin_cos();
+        Self::from_affine(
+            [cos * scale_x, sin * scale_x],
+            [-sin * scale_y, cos * scale_y],
+            translation,
+        )
+    }
+
+    /// Create a transform from an arbitrary affine transformation,
+    /// given the images of the local x and y axes, and the translation
+    ///
+    /// The linear part may contain any combination of rotation, scale, mirroring and skew.
+    /// If it is singular, the shape collapses to a segment or a point.
+    #[inline]
+    #[must_use]
+    pub fn from_affine(
+        x_axis: impl Into<[S; 2]>,
+        y_axis: impl Into<[S; 2]>,
+        translation: impl Into<[S; 2]>,
+    ) -> Self {
+        Self {
+            x_axis: x_axis.into().into(),
+            y_axis: y_axis.into().into(),
+            translation: translation.into().into(),
+        }
+    }
+
+    /// Returns the translation of the transform
+    #[inline]
+    #[must_use]
+    pub fn translation(&self) -> [S; 2] {
+        self.translation.into()
+    }
+
+    /// Maps a point from the local space to the world space
+    #[inline]
+    #[must_use]
+    pub fn transform_point(&self, point: impl Into<[S; 2]>) -> [S; 2] {
+        self.transform_vector(point.into().into()).into()
+    }
+
+    pub(super) fn position(&self) -> Vector<S> {
+        self.translation
+    }
+
+    /// Maps a local point to the world space
+    pub(super) fn transform_vector(&self, point: Vector<S>) -> Vector<S> {
+        self.x_axis * point.x + self.y_axis * point.y + self.translation
+    }
+
+    /// Maps a world direction to the local direction in which the (untransformed) shape has the same support point
+    ///
+    /// (This is the transpose of the linear part, see [`crate::Transform`])
+    pub(super) fn direction_to_local(&self, direction: Vector<S>) -> Vector<S> {
+        Vector::new(self.x_axis.dot(direction), self.y_axis.dot(direction))
+    }
+}
+
+impl<S: Real> Default for Transform<S> {
+    /// The default transform is the identity transform
+    #[inline]
+    fn default() -> Self {
+        Self {
+            x_axis: Vector::new(S::ONE, S::ZERO),
+            y_axis: Vector::new(S::ZERO, S::ONE),
+            translation: Vector::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_ulps_eq;
+    use core::f64::consts;
+
+    use super::*;
+
+    #[test]
+    fn equivalent_to_f32_transform() {
+        let transform =
+            Transform::from_scale_angle_translation([2.0, -0.5], consts::FRAC_PI_3, [1.0, 2.0]);
+        let expected = crate::Transform::from_scale_angle_translation(
+            [2.0, -0.5],
+            core::f32::consts::FRAC_PI_3,
+            [1.0, 2.0],
+        )
+        .transform_point([0.5, 3.0]);
+        let [x, y] = transform.transform_point([0.5, 3.0]);
+        assert_ulps_eq!(x, f64::from(expected[0]), epsilon = 1e-6);
+        assert_ulps_eq!(y, f64::from(expected[1]), epsilon = 1e-6);
+    }
+}
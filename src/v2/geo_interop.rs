@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+
+use geo::{
+    Geometry, GeometryCollection, Line, LineString, Polygon, Rect, Triangle, TriangulateEarcut,
+};
+
+use super::{
+    shapes::{ConvexPolygon, Segment, ShapeData},
+    CollisionShape, CompoundShape, Transform,
+};
+
+impl From<Line<f32>> for ShapeData {
+    fn from(line: Line<f32>) -> Self {
+        Segment::new(line.start, line.end).into()
+    }
+}
+
+impl From<Triangle<f32>> for ShapeData {
+    fn from(triangle: Triangle<f32>) -> Self {
+        ConvexPolygon::new(triangle.to_array()).into()
+    }
+}
+
+impl From<Rect<f32>> for CollisionShape {
+    fn from(rect: Rect<f32>) -> Self {
+        CollisionShape::new_rectangle(rect.width(), rect.height())
+            .with_transform(Transform::from_translation(rect.center()))
+    }
+}
+
+impl From<&Polygon<f32>> for CompoundShape {
+    /// Polygons without holes are decomposed into convex polygons (see [`CompoundShape::new_polygon`]),
+    /// polygons with holes are decomposed into triangles
+    fn from(polygon: &Polygon<f32>) -> Self {
+        if polygon.interiors().is_empty() {
+            return CompoundShape::new_polygon(polygon.exterior().coords().copied());
+        }
+        polygon
+            .earcut_triangles_iter()
+            .map(CollisionShape::from)
+            .collect()
+    }
+}
+
+impl From<Polygon<f32>> for CompoundShape {
+    fn from(polygon: Polygon<f32>) -> Self {
+        Self::from(&polygon)
+    }
+}
+
+impl From<&LineString<f32>> for CompoundShape {
+    /// Each line of the line string becomes a [`Segment`]
+    fn from(line_string: &LineString<f32>) -> Self {
+        line_string.lines().map(CollisionShape::from).collect()
+    }
+}
+
+impl From<LineString<f32>> for CompoundShape {
+    fn from(line_string: LineString<f32>) -> Self {
+        Self::from(&line_string)
+    }
+}
+
+impl CollisionShape {
+    /// Returns the shape in world space as a [`geo`] geometry
+    ///
+    /// Segments become a [`Line`], and the other shapes a [`Polygon`].
+    /// Circles are approximated, so that no vertex is further than `tolerance` from the actual circle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::{CollisionShape, Transform};
+    /// use geo::{Area, Geometry};
+    /// let shape = CollisionShape::new_rectangle(2.0, 3.0)
+    ///     .with_transform(Transform::from_angle_translation(0.5, [1.0, 2.0]));
+    /// let Geometry::Polygon(polygon) = shape.to_geo(0.01) else { unreachable!() };
+    /// assert!((polygon.unsigned_area() - 6.0).abs() < 1e-5);
+    /// ```
+    #[must_use]
+    pub fn to_geo(&self, tolerance: f32) -> Geometry<f32> {
+        let outline = self.world_outline(tolerance);
+        if let (ShapeData::Segment(_), [start, end]) = (&self.data, outline.as_slice()) {
+            return Line::new(<[f32; 2]>::from(*start), <[f32; 2]>::from(*end)).into();
+        }
+        let exterior: Vec<[f32; 2]> = outline.into_iter().map(Into::into).collect();
+        Polygon::new(exterior.into(), Vec::new()).into()
+    }
+}
+
+impl CompoundShape {
+    /// Returns the parts of the compound shape in world space as [`geo`] geometries
+    ///
+    /// See [`CollisionShape::to_geo`]
+    #[must_use]
+    pub fn to_geo(&self, tolerance: f32) -> GeometryCollection<f32> {
+        self.shapes()
+            .iter()
+            .map(|shape| shape.to_geo(tolerance))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use geo::{coord, line_string, polygon, Area};
+
+    use super::*;
+
+    #[test]
+    fn line_becomes_segment() {
+        let shape = CollisionShape::from(Line::new(
+            coord! { x: 0.0, y: 0.0 },
+            coord! { x: 2.0, y: 0.0 },
+        ));
+        assert!(shape.is_collided_with(&CollisionShape::new_circle(0.5)));
+        assert_eq!(
+            shape.to_geo(0.01),
+            Geometry::Line(Line::new(
+                coord! { x: 0.0, y: 0.0 },
+                coord! { x: 2.0, y: 0.0 }
+            ))
+        );
+    }
+
+    #[test]
+    fn rect_becomes_rectangle() {
+        let rect = Rect::new(coord! { x: 1.0, y: 2.0 }, coord! { x: 4.0, y: 3.0 });
+        let shape = CollisionShape::from(rect);
+        let Geometry::Polygon(polygon) = shape.to_geo(0.01) else {
+            panic!("expected a polygon");
+        };
+        assert_abs_diff_eq!(polygon.unsigned_area(), 3.0);
+        assert_abs_diff_eq!(shape.mass_properties(1.0).centroid[0], 2.5);
+    }
+
+    #[test]
+    fn triangle_becomes_convex_polygon() {
+        let triangle = Triangle::new(
+            coord! { x: 0.0, y: 0.0 },
+            coord! { x: 0.0, y: 2.0 },
+            coord! { x: 2.0, y: 0.0 },
+        );
+        let shape = CollisionShape::from(triangle);
+        assert_abs_diff_eq!(shape.mass_properties(1.0).area, 2.0);
+    }
+
+    #[test]
+    fn convex_polygon_is_a_single_shape() {
+        let polygon =
+            polygon![(x: 0.0, y: 0.0), (x: 2.0, y: 0.0), (x: 2.0, y: 1.0), (x: 0.0, y: 1.0)];
+        let compound = CompoundShape::from(&polygon);
+        assert_eq!(compound.shapes().len(), 1);
+        assert_abs_diff_eq!(compound.shapes()[0].mass_properties(1.0).area, 2.0);
+    }
+
+    #[test]
+    fn concave_polygon_is_decomposed() {
+        // An L shape
+        let polygon = polygon![
+            (x: 0.0, y: 0.0),
+            (x: 3.0, y: 0.0),
+            (x: 3.0, y: 1.0),
+            (x: 1.0, y: 1.0),
+            (x: 1.0, y: 3.0),
+            (x: 0.0, y: 3.0),
+        ];
+        let compound = CompoundShape::from(&polygon);
+        let area: f32 = compound
+            .shapes()
+            .iter()
+            .map(|s| s.mass_properties(1.0).area)
+            .sum();
+        assert_abs_diff_eq!(area, 5.0, epsilon = 1e-5);
+        assert_eq!(compound.shapes().len(), 2);
+        let circle = |x, y| {
+            CollisionShape::new_circle(0.4).with_transform(Transform::from_translation([x, y]))
+        };
+        assert!(compound.is_collided_with(&circle(0.5, 2.5)));
+        assert!(compound.is_collided_with(&circle(2.5, 0.5)));
+        assert!(!compound.is_collided_with(&circle(2.5, 2.5)));
+    }
+
+    #[test]
+    fn polygon_with_hole_is_decomposed() {
+        let polygon = polygon![
+            exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 4.0)],
+            interiors: [[(x: 1.0, y: 1.0), (x: 3.0, y: 1.0), (x: 3.0, y: 3.0), (x: 1.0, y: 3.0)]],
+        ];
+        let compound = CompoundShape::from(polygon);
+        let area: f32 = compound
+            .shapes()
+            .iter()
+            .map(|s| s.mass_properties(1.0).area)
+            .sum();
+        assert_abs_diff_eq!(area, 12.0, epsilon = 1e-5);
+        let center =
+            CollisionShape::new_circle(0.5).with_transform(Transform::from_translation([2.0, 2.0]));
+        assert!(!compound.is_collided_with(&center));
+    }
+
+    #[test]
+    fn line_string_becomes_segments() {
+        let line_string = line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 1.0, y: 1.0)];
+        let compound = CompoundShape::from(&line_string);
+        assert_eq!(compound.shapes().len(), 2);
+        assert_eq!(compound.to_geo(0.01).len(), 2);
+    }
+}
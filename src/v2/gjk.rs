@@ -1,35 +1,115 @@
-use core::ops::{Neg, Sub};
+use core::ops::{Add, Div, Mul, Neg, Sub};
 
 use super::{math::*, Support};
 
+/// Returns a simplex enclosing the origin if the shape contains the origin.
+///
+/// Otherwise, returns the direction that proved the origin to be outside the shape (a separating axis).
+/// That direction is a good candidate for the `initial_direction` of the next query, if the shape did not change much.
+///
+/// If the origin is not proven inside the shape after `max_iterations`, the last direction is returned.
 pub(super) fn find_simplex_enclosing_origin<V>(
     shape: &impl Support<V>,
     initial_direction: V,
-) -> Option<Simplex<V>>
+    max_iterations: usize,
+) -> Result<Simplex<V>, NotEnclosed<V>>
 where
     V: Copy + Dot + Perp + Neg<Output = V> + Sub<V, Output = V>,
     <V as Dot>::Output: CmpToZero,
 {
     let mut simplex = {
         let first_point = shape.support(initial_direction);
-        if is_negative_or_invalid(first_point.dot(initial_direction)) {
-            return None;
-        }
+        check_support(first_point.dot(initial_direction), initial_direction)?;
         Simplex::new(first_point)
     };
 
-    while let Some(direction) = simplex.next() {
+    for _ in 0..max_iterations {
+        let Some(direction) = simplex.next() else {
+            return Ok(simplex);
+        };
         let point = shape.support(direction);
-        if is_negative_or_invalid(point.dot(direction)) {
-            return None;
+        check_support(point.dot(direction), direction)?;
+        simplex.insert(point);
+    }
+    match simplex.next() {
+        None => Ok(simplex),
+        Some(direction) => Err(NotEnclosed::NotConverged(direction)),
+    }
+}
+
+/// Reason why [`find_simplex_enclosing_origin`] did not return a simplex
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) enum NotEnclosed<V> {
+    /// The direction proved the origin to be outside the shape (a separating axis)
+    Separated(V),
+    /// The support point in that direction is invalid (not a number)
+    Invalid(V),
+    /// The maximum number of iterations was reached before proving the origin inside or outside the shape
+    ///
+    /// With the last direction tested
+    NotConverged(V),
+}
+
+impl<V> NotEnclosed<V> {
+    /// Returns the last direction tested
+    pub(super) fn direction(self) -> V {
+        match self {
+            Self::Separated(direction)
+            | Self::Invalid(direction)
+            | Self::NotConverged(direction) => direction,
+        }
+    }
+}
+
+/// Returns the point of `shape` that is the closest to the origin
+///
+/// If the origin is inside the shape, returns instead a simplex enclosing the origin (that can be used to run EPA)
+pub(super) fn find_closest_point_to_origin<S, V>(
+    shape: &impl Support<V>,
+    initial_direction: V,
+    max_iterations: usize,
+) -> Result<V, Simplex<V>>
+where
+    V: Copy
+        + PartialEq
+        + Add<V, Output = V>
+        + Sub<V, Output = V>
+        + Neg<Output = V>
+        + Mul<S, Output = V>
+        + Dot<Output = S>
+        + Cross<Output = S>,
+    S: Copy + PartialOrd + Sub<S, Output = S> + Div<S, Output = S> + CmpToZero,
+{
+    let mut simplex = Simplex::new(shape.support(initial_direction));
+    let mut closest = simplex.closest_to_origin().ok_or(simplex)?;
+    for _ in 0..max_iterations {
+        let distance_squared = closest.magnitude_squared();
+        if !distance_squared.is_positive() {
+            return Err(simplex);
+        }
+        let point = shape.support(-closest);
+        if !(distance_squared - closest.dot(point)).is_positive() || simplex.contains(point) {
+            break;
         }
         simplex.insert(point);
+        match simplex.closest_to_origin() {
+            None => return Err(simplex),
+            Some(next) if next.magnitude_squared() < distance_squared => closest = next,
+            Some(_) => break,
+        }
     }
-    Some(simplex)
+    Ok(closest)
 }
 
-fn is_negative_or_invalid(dot: impl CmpToZero) -> bool {
-    !dot.is_positive() && !dot.is_zero()
+/// Checks that the support point in `direction` (at `dot` along the direction) is not proving the origin outside the shape
+fn check_support<V>(dot: impl CmpToZero, direction: V) -> Result<(), NotEnclosed<V>> {
+    if dot.is_negative() {
+        Err(NotEnclosed::Separated(direction))
+    } else if !dot.is_positive() && !dot.is_zero() {
+        Err(NotEnclosed::Invalid(direction))
+    } else {
+        Ok(())
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -103,6 +183,84 @@ where
     }
 }
 
+impl<P: Copy + PartialEq> Simplex<P> {
+    fn contains(&self, point: P) -> bool {
+        match self {
+            Self::Point(p) => *p == point,
+            Self::Line(p1, p2) => *p1 == point || *p2 == point,
+            Self::Triangle(p1, p2, p3) => *p1 == point || *p2 == point || *p3 == point,
+        }
+    }
+}
+
+impl<S, V> Simplex<V>
+where
+    V: Copy
+        + Add<V, Output = V>
+        + Sub<V, Output = V>
+        + Neg<Output = V>
+        + Mul<S, Output = V>
+        + Dot<Output = S>
+        + Cross<Output = S>,
+    S: Copy + PartialOrd + Div<S, Output = S> + CmpToZero,
+{
+    /// Set to the simpler simplex that contains the point the closest to the origin, and returns that point.
+    ///
+    /// Returns `None` if the origin is inside the simplex.
+    fn closest_to_origin(&mut self) -> Option<V> {
+        match *self {
+            Self::Point(point) => Some(point),
+            Self::Line(p1, p2) => Some(self.reduce_to_segment(p1, p2)),
+            Self::Triangle(p1, p2, p3) => {
+                let c1 = (p2 - p1).cross(-p1);
+                let c2 = (p3 - p2).cross(-p2);
+                let c3 = (p1 - p3).cross(-p3);
+                let has_negative = c1.is_negative() || c2.is_negative() || c3.is_negative();
+                let has_positive = c1.is_positive() || c2.is_positive() || c3.is_positive();
+                if !(has_negative && has_positive) {
+                    return None;
+                }
+                [(p1, p2), (p2, p3), (p3, p1)]
+                    .into_iter()
+                    .map(|(a, b)| {
+                        let mut edge = Self::Line(a, b);
+                        let closest = edge.reduce_to_segment(a, b);
+                        (edge, closest, closest.magnitude_squared())
+                    })
+                    .reduce(|best, candidate| {
+                        if candidate.2 < best.2 {
+                            candidate
+                        } else {
+                            best
+                        }
+                    })
+                    .map(|(edge, closest, _)| {
+                        *self = edge;
+                        closest
+                    })
+            }
+        }
+    }
+
+    /// Set to the simpler simplex containing the closest point to the origin on the segment `p1`-`p2`,
+    /// and returns that point.
+    fn reduce_to_segment(&mut self, p1: V, p2: V) -> V {
+        let edge = p2 - p1;
+        let projection = (-p1).dot(edge);
+        if !projection.is_positive() {
+            *self = Self::Point(p1);
+            return p1;
+        }
+        let length_squared = edge.magnitude_squared();
+        if projection >= length_squared {
+            *self = Self::Point(p2);
+            return p2;
+        }
+        *self = Self::Line(p1, p2);
+        p1 + edge * (projection / length_squared)
+    }
+}
+
 /// Returns a perpendicular to `axis` that has a positive dot product with `direction`
 fn perp<V>(axis: V, direction: V) -> V
 where
@@ -134,7 +292,100 @@ mod tests {
 
     #[test]
     fn invalid_support() {
-        assert!(find_simplex_enclosing_origin(&InvalidSupport, Vec2::X).is_none());
+        assert_eq!(
+            find_simplex_enclosing_origin(&InvalidSupport, Vec2::X, 1000),
+            Err(NotEnclosed::Invalid(Vec2::X))
+        );
+    }
+
+    struct CountingSupport<S> {
+        shape: S,
+        count: core::cell::Cell<usize>,
+    }
+
+    impl<S: Support<Vec2>> Support<Vec2> for CountingSupport<S> {
+        fn support(&self, direction: Vec2) -> Vec2 {
+            self.count.set(self.count.get() + 1);
+            self.shape.support(direction)
+        }
+    }
+
+    #[test]
+    fn separating_axis_can_be_reused_as_initial_direction() {
+        let shape = CountingSupport {
+            shape: Square(Vec2::new(3.0, 2.5)),
+            count: core::cell::Cell::new(0),
+        };
+        let axis = find_simplex_enclosing_origin(&shape, Vec2::new(1.0, -1.0), 1000)
+            .unwrap_err()
+            .direction();
+        assert!(shape.count.get() > 1);
+        shape.count.set(0);
+        assert_eq!(
+            find_simplex_enclosing_origin(&shape, axis, 1000),
+            Err(NotEnclosed::Separated(axis))
+        );
+        assert_eq!(shape.count.get(), 1);
+    }
+
+    #[test]
+    fn gives_up_after_max_iterations() {
+        let shape = Square(Vec2::new(0.5, 0.5));
+        assert!(find_simplex_enclosing_origin(&shape, Vec2::X, 1000).is_ok());
+        assert!(matches!(
+            find_simplex_enclosing_origin(&shape, Vec2::X, 0),
+            Err(NotEnclosed::NotConverged(_))
+        ));
+    }
+
+    #[test]
+    fn closest_point_of_invalid_support() {
+        assert!(find_closest_point_to_origin(&InvalidSupport, Vec2::X, 1000).is_err());
+    }
+
+    struct Square(Vec2);
+    impl Support<Vec2> for Square {
+        fn support(&self, direction: Vec2) -> Vec2 {
+            self.0 + Vec2::new(direction.x.signum(), direction.y.signum())
+        }
+    }
+
+    #[rstest]
+    #[case(Square(Vec2::new(3.0, 0.0)), Vec2::new(2.0, 0.0))]
+    #[case(Square(Vec2::new(0.0, -3.0)), Vec2::new(0.0, -2.0))]
+    #[case(Square(Vec2::new(3.0, 0.5)), Vec2::new(2.0, 0.0))]
+    #[case(Square(Vec2::new(3.0, 3.0)), Vec2::new(2.0, 2.0))]
+    #[cfg(feature = "std")]
+    fn closest_point_to_origin(#[case] shape: Square, #[case] expected: Vec2) {
+        for initial_direction in [Vec2::X, Vec2::Y, -Vec2::X, -Vec2::Y, Vec2::ONE] {
+            assert_eq!(
+                find_closest_point_to_origin(&shape, initial_direction, 1000),
+                Ok(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn closest_point_returns_simplex_when_origin_is_enclosed() {
+        let result = find_closest_point_to_origin(&Square(Vec2::new(0.5, 0.0)), Vec2::X, 1000);
+        assert!(result.is_err(), "{result:?}");
+    }
+
+    #[test]
+    fn closest_point_on_triangle() {
+        let mut simplex =
+            Simplex::Triangle(Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0), Vec2::X * 2.0);
+        assert_eq!(simplex.closest_to_origin(), Some(Vec2::X));
+        assert_eq!(
+            simplex,
+            Simplex::Line(Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn closest_point_on_triangle_enclosing_origin() {
+        let mut simplex = Simplex::Triangle(Vec2::new(-1.0, -1.0), Vec2::new(1.0, -1.0), Vec2::Y);
+        assert_eq!(simplex.closest_to_origin(), None);
     }
 
     #[rstest]
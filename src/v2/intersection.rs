@@ -0,0 +1,202 @@
+use alloc::vec::Vec;
+
+use glam::Vec2;
+
+use super::CollisionShape;
+
+/// Overlapping region of two shapes
+///
+/// See [`CollisionShape::intersection`]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Intersection<S = f32, V = [S; 2]> {
+    /// Area of the overlapping region
+    pub area: S,
+    /// Center of mass of the overlapping region
+    pub centroid: V,
+    /// Vertices of the convex polygon covering the overlapping region, in counter-clockwise order
+    pub polygon: Vec<V>,
+}
+
+impl CollisionShape {
+    /// Returns the region where the two shapes overlap, with its area and centroid.
+    /// Returns `None` if the shapes don't overlap, or if the overlap has no area.
+    ///
+    /// Circles are approximated by polygons, so that no point of the approximation is further than `tolerance`
+    /// from the actual circle. (The number of vertices per circle is limited to 1024, whatever the tolerance.)
+    ///
+    /// Segments have no area, so the intersection with a segment is always `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_ulps_eq;
+    /// use impacted::{CollisionShape, Transform};
+    /// let water = CollisionShape::new_rectangle(10.0, 4.0)
+    ///     .with_transform(Transform::from_translation([0.0, -2.0]));
+    /// let boat = CollisionShape::new_rectangle(2.0, 1.0)
+    ///     .with_transform(Transform::from_translation([1.0, 0.25]));
+    /// let submerged = boat.intersection(&water, 0.01).unwrap();
+    /// assert_ulps_eq!(submerged.area, 0.5);
+    /// assert_ulps_eq!(submerged.centroid[1], -0.125);
+    /// ```
+    #[must_use]
+    pub fn intersection(&self, other: &Self, tolerance: f32) -> Option<Intersection> {
+        let mut polygon = self.world_outline(tolerance);
+        let clip = other.world_outline(tolerance);
+        // Clipping by an outline without area (like a point or a segment) would leave the polygon unchanged
+        if area_centroid(&polygon).is_none() || area_centroid(&clip).is_none() {
+            return None;
+        }
+        for (index, &start) in clip.iter().enumerate() {
+            let end = clip[(index + 1) % clip.len()];
+            polygon = clip_polygon(&polygon, start, end);
+        }
+        let (area, centroid) = area_centroid(&polygon)?;
+        Some(Intersection {
+            area,
+            centroid: centroid.into(),
+            polygon: polygon.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+/// Returns the part of the `polygon` that is on the left of the line going from `start` to `end`
+///
+/// (Sutherland-Hodgman algorithm)
+fn clip_polygon(polygon: &[Vec2], start: Vec2, end: Vec2) -> Vec<Vec2> {
+    let axis = end - start;
+    let side = |point: Vec2| axis.perp_dot(point - start);
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+    for (index, &current) in polygon.iter().enumerate() {
+        let next = polygon[(index + 1) % polygon.len()];
+        let (current_side, next_side) = (side(current), side(next));
+        if current_side >= 0.0 {
+            result.push(current);
+        }
+        if (current_side < 0.0) != (next_side < 0.0) {
+            let t = current_side / (current_side - next_side);
+            result.push(current + (next - current) * t);
+        }
+    }
+    result
+}
+
+/// Returns the area and centroid of a counter-clockwise polygon
+///
+/// Returns `None` if the polygon has no area
+fn area_centroid(polygon: &[Vec2]) -> Option<(f32, Vec2)> {
+    let origin = *polygon.first()?;
+    let mut double_area = 0.0;
+    let mut weighted_sum = Vec2::ZERO;
+    for window in polygon[1..].windows(2) {
+        let (p1, p2) = (window[0] - origin, window[1] - origin);
+        let cross = p1.perp_dot(p2);
+        double_area += cross;
+        weighted_sum += (p1 + p2) * cross;
+    }
+    if double_area <= 0.0 {
+        return None;
+    }
+    Some((
+        double_area * 0.5,
+        origin + weighted_sum / (3.0 * double_area),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::Transform;
+
+    use super::*;
+
+    #[test]
+    fn overlapping_rectangles() {
+        let shape1 = CollisionShape::new_rectangle(2.0, 2.0);
+        let shape2 = CollisionShape::new_rectangle(2.0, 2.0)
+            .with_transform(Transform::from_translation([1.0, 1.5]));
+        let intersection = shape1.intersection(&shape2, 0.01).unwrap();
+        assert_abs_diff_eq!(intersection.area, 0.5, epsilon = 1e-6);
+        assert_abs_diff_eq!(
+            Vec2::from(intersection.centroid),
+            Vec2::new(0.5, 0.75),
+            epsilon = 1e-6
+        );
+        assert_eq!(intersection.polygon.len(), 4);
+    }
+
+    #[test]
+    fn no_overlap() {
+        let shape1 = CollisionShape::new_circle(1.0);
+        let shape2 =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([3.0, 0.0]));
+        assert_eq!(shape1.intersection(&shape2, 0.01), None);
+    }
+
+    #[test]
+    fn segment_has_no_area() {
+        let shape1 = CollisionShape::new_rectangle(2.0, 2.0);
+        let shape2 = CollisionShape::new_segment([-2.0, 0.0], [2.0, 0.0]);
+        assert_eq!(shape1.intersection(&shape2, 0.01), None);
+        assert_eq!(shape2.intersection(&shape1, 0.01), None);
+    }
+
+    #[test]
+    fn shape_without_area_has_no_intersection() {
+        let rect = CollisionShape::new_rectangle(2.0, 2.0);
+        let point = CollisionShape::new_circle(1.0).with_transform(
+            Transform::from_scale_angle_translation([0.0, 0.0], 0.0, [5.0, 5.0]),
+        );
+        let empty = CollisionShape::new_convex_polygon(Vec::<[f32; 2]>::new());
+        for shape in [point, empty] {
+            assert_eq!(rect.intersection(&shape, 0.01), None);
+            assert_eq!(shape.intersection(&rect, 0.01), None);
+        }
+    }
+
+    #[test]
+    fn circle_inside_rectangle_is_approximated_within_tolerance() {
+        let circle =
+            CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([0.5, 0.0]));
+        let rect = CollisionShape::new_rectangle(4.0, 4.0);
+        for tolerance in [0.1, 0.01, 0.001] {
+            let intersection = circle.intersection(&rect, tolerance).unwrap();
+            assert!(intersection.area <= consts::PI);
+            assert!(intersection.area >= consts::PI * (1.0 - tolerance) * (1.0 - tolerance));
+            assert_abs_diff_eq!(
+                Vec2::from(intersection.centroid),
+                Vec2::new(0.5, 0.0),
+                epsilon = 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn half_circle() {
+        let circle = CollisionShape::new_circle(1.0);
+        let rect = CollisionShape::new_rectangle(4.0, 2.0).with_transform(
+            Transform::from_angle_translation(consts::FRAC_PI_2, [1.0, 0.0]),
+        );
+        let intersection = circle.intersection(&rect, 0.0001).unwrap();
+        assert_abs_diff_eq!(intersection.area, consts::FRAC_PI_2, epsilon = 1e-3);
+        assert_abs_diff_eq!(
+            intersection.centroid[0],
+            4.0 / (3.0 * consts::PI),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn mirrored_shape_outline_is_counter_clockwise() {
+        let shape = CollisionShape::new_rectangle(2.0, 2.0).with_transform(
+            Transform::from_scale_angle_translation([-1.0, 1.0], 0.0, [0.0, 0.0]),
+        );
+        let (area, _) = area_centroid(&shape.world_outline(0.01)).unwrap();
+        assert_abs_diff_eq!(area, 4.0);
+    }
+}
@@ -0,0 +1,264 @@
+use core::f32::consts;
+
+use glam::{Affine2, Mat2, Vec2};
+
+use super::{
+    shapes::{Circle, ConvexPolygon, Rectangle, Segment, ShapeData},
+    CollisionShape,
+};
+
+/// Mass properties of a shape
+///
+/// See [`ShapeData::mass_properties`] and [`CollisionShape::mass_properties`]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MassProperties<S = f32, V = [S; 2]> {
+    /// Area covered by the shape
+    pub area: S,
+    /// Mass of the shape (area multiplied by density)
+    pub mass: S,
+    /// Center of mass
+    pub centroid: V,
+    /// Moment of inertia around the center of mass
+    pub inertia: S,
+}
+
+/// Area, centroid and second moment of area (per unit of density) of a shape
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct AreaMoments {
+    area: f32,
+    centroid: Vec2,
+    /// Second moment of area tensor (`∫ p * pᵀ dA`), relative to the centroid
+    second_moment: Mat2,
+}
+
+impl AreaMoments {
+    /// Returns the moments of the shape after being transformed by `transform`
+    ///
+    /// For a linear part `M`, the area is scaled by `|det(M)|` and the second moment tensor becomes `|det(M)| * M * J * Mᵀ`,
+    /// which remains exact for non-uniform scales.
+    fn transformed(self, transform: &Affine2) -> Self {
+        let linear = transform.matrix2;
+        let scale = linear.determinant().abs();
+        Self {
+            area: self.area * scale,
+            centroid: transform.transform_point2(self.centroid),
+            second_moment: linear * self.second_moment * linear.transpose() * scale,
+        }
+    }
+
+    fn with_density(self, density: f32) -> MassProperties {
+        MassProperties {
+            area: self.area,
+            mass: self.area * density,
+            centroid: self.centroid.into(),
+            inertia: (self.second_moment.x_axis.x + self.second_moment.y_axis.y) * density,
+        }
+    }
+}
+
+impl Circle {
+    fn area_moments(self) -> AreaMoments {
+        let radius_squared = self.radius() * self.radius();
+        let area = consts::PI * radius_squared;
+        AreaMoments {
+            area,
+            centroid: Vec2::ZERO,
+            second_moment: Mat2::from_diagonal(Vec2::splat(area * radius_squared * 0.25)),
+        }
+    }
+}
+
+impl Rectangle {
+    fn area_moments(self) -> AreaMoments {
+        let size = Vec2::from(self.half_extents()) * 2.0;
+        let area = size.x * size.y;
+        AreaMoments {
+            area,
+            centroid: Vec2::ZERO,
+            second_moment: Mat2::from_diagonal(size * size * (area / 12.0)),
+        }
+    }
+}
+
+impl Segment {
+    fn area_moments(&self) -> AreaMoments {
+        let [p1, p2] = self.points();
+        AreaMoments {
+            area: 0.0,
+            centroid: (Vec2::from(p1) + Vec2::from(p2)) * 0.5,
+            second_moment: Mat2::ZERO,
+        }
+    }
+}
+
+impl ConvexPolygon {
+    /// Sums the contributions of the triangles between the origin and each edge
+    #[allow(clippy::cast_precision_loss)]
+    fn area_moments(&self) -> AreaMoments {
+        let vertices = self.vertices_slice();
+        let edges = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(&p1, &p2)| (p1, p2, p1.perp_dot(p2)));
+        let (mut area, mut first_moment) = (0.0, Vec2::ZERO);
+        let (mut xx, mut yy, mut xy) = (0.0, 0.0, 0.0);
+        for (p1, p2, cross) in edges {
+            area += cross * 0.5;
+            first_moment += (p1 + p2) * (cross / 6.0);
+            xx += (p1.x * p1.x + p1.x * p2.x + p2.x * p2.x) * (cross / 12.0);
+            yy += (p1.y * p1.y + p1.y * p2.y + p2.y * p2.y) * (cross / 12.0);
+            xy += (2.0 * (p1.x * p1.y + p2.x * p2.y) + p1.x * p2.y + p2.x * p1.y) * (cross / 24.0);
+        }
+        if area <= 0.0 {
+            // Points and segments have no area
+            return AreaMoments {
+                area: 0.0,
+                centroid: vertices.iter().sum::<Vec2>() / vertices.len().max(1) as f32,
+                second_moment: Mat2::ZERO,
+            };
+        }
+        let centroid = first_moment / area;
+        AreaMoments {
+            area,
+            centroid,
+            second_moment: Mat2::from_cols(Vec2::new(xx, xy), Vec2::new(xy, yy))
+                - Mat2::from_cols(centroid * centroid.x, centroid * centroid.y) * area,
+        }
+    }
+}
+
+impl ShapeData {
+    pub(crate) fn area_moments(&self) -> AreaMoments {
+        match self {
+            ShapeData::Circle(circle) => circle.area_moments(),
+            ShapeData::Rectangle(rect) => rect.area_moments(),
+            ShapeData::Segment(segment) => segment.area_moments(),
+            ShapeData::ConvexPolygon(polygon) => polygon.area_moments(),
+        }
+    }
+
+    /// Returns the area, mass, centroid and moment of inertia of the shape for the given density
+    ///
+    /// The values are expressed in the local space of the shape (ignoring any [`Transform`](crate::Transform)).
+    /// Use [`CollisionShape::mass_properties`] to get the mass properties of a transformed shape.
+    ///
+    /// Segments have no area, and therefore a mass and inertia of zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_ulps_eq;
+    /// use impacted::shapes::{Rectangle, ShapeData};
+    /// let properties = ShapeData::from(Rectangle::new(2.0, 3.0)).mass_properties(2.0);
+    /// assert_ulps_eq!(properties.area, 6.0);
+    /// assert_ulps_eq!(properties.mass, 12.0);
+    /// assert_ulps_eq!(properties.inertia, 13.0);
+    /// ```
+    #[must_use]
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        self.area_moments().with_density(density)
+    }
+}
+
+impl CollisionShape {
+    /// Returns the area, mass, centroid and moment of inertia of the shape for the given density
+    ///
+    /// The values are expressed in world space, taking the [`Transform`](crate::Transform) into account
+    /// (including non-uniform scale).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_ulps_eq;
+    /// use impacted::{CollisionShape, Transform};
+    /// let shape = CollisionShape::new_rectangle(1.0, 1.0)
+    ///     .with_transform(Transform::from_scale_angle_translation([2.0, 3.0], 0.0, [1.0, 0.0]));
+    /// let properties = shape.mass_properties(1.0);
+    /// assert_ulps_eq!(properties.area, 6.0);
+    /// assert_ulps_eq!(properties.centroid[0], 1.0);
+    /// assert_ulps_eq!(properties.inertia, 6.5);
+    /// ```
+    #[must_use]
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        self.data
+            .area_moments()
+            .transformed(self.transform.local_to_world())
+            .with_density(density)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use rstest::rstest;
+
+    use crate::Transform;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Circle::new(1.0).into(), consts::PI, consts::FRAC_PI_2)]
+    #[case(Circle::new(2.0).into(), consts::PI * 4.0, consts::PI * 8.0)]
+    #[case(Rectangle::new(1.0, 1.0).into(), 1.0, 1.0 / 6.0)]
+    #[case(Rectangle::new(2.0, 4.0).into(), 8.0, 40.0 / 3.0)]
+    #[case(Segment::new([0.0, 0.0], [1.0, 0.0]).into(), 0.0, 0.0)]
+    #[case(ConvexPolygon::new([[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]]).into(), 1.0, 1.0 / 6.0)]
+    #[case(ConvexPolygon::new([[3.0, 1.0], [5.0, 1.0], [5.0, 5.0], [3.0, 5.0]]).into(), 8.0, 40.0 / 3.0)]
+    #[case(ConvexPolygon::new([[0.0, 0.0], [3.0, 0.0], [0.0, 3.0]]).into(), 4.5, 4.5)]
+    fn unit_density(#[case] shape: ShapeData, #[case] area: f32, #[case] inertia: f32) {
+        let properties = shape.mass_properties(1.0);
+        assert_abs_diff_eq!(properties.area, area, epsilon = 1e-5);
+        assert_abs_diff_eq!(properties.mass, area, epsilon = 1e-5);
+        assert_abs_diff_eq!(properties.inertia, inertia, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn mass_and_inertia_scale_with_density() {
+        let shape = ShapeData::from(Rectangle::new(2.0, 4.0));
+        let properties = shape.mass_properties(3.0);
+        assert_abs_diff_eq!(properties.area, 8.0);
+        assert_abs_diff_eq!(properties.mass, 24.0);
+        assert_abs_diff_eq!(properties.inertia, 40.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn segment_centroid_is_its_middle() {
+        let properties = ShapeData::from(Segment::new([1.0, 2.0], [3.0, 6.0])).mass_properties(1.0);
+        assert_abs_diff_eq!(Vec2::from(properties.centroid), Vec2::new(2.0, 4.0));
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(consts::FRAC_PI_4)]
+    #[case(1.0)]
+    fn scaled_rectangle_is_equivalent_to_bigger_rectangle(#[case] angle: f32) {
+        let scaled = CollisionShape::new_rectangle(1.0, 1.0).with_transform(
+            Transform::from_scale_angle_translation([2.0, 4.0], angle, [1.0, 2.0]),
+        );
+        let bigger = CollisionShape::new_rectangle(2.0, 4.0)
+            .with_transform(Transform::from_angle_translation(angle, [1.0, 2.0]));
+        let expected = bigger.mass_properties(2.0);
+        let actual = scaled.mass_properties(2.0);
+        assert_abs_diff_eq!(actual.area, expected.area, epsilon = 1e-5);
+        assert_abs_diff_eq!(actual.mass, expected.mass, epsilon = 1e-5);
+        assert_abs_diff_eq!(actual.inertia, expected.inertia, epsilon = 1e-4);
+        assert_abs_diff_eq!(
+            Vec2::from(actual.centroid),
+            Vec2::new(1.0, 2.0),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn non_uniformly_scaled_circle_is_an_ellipse() {
+        let shape = CollisionShape::new_circle(1.0).with_transform(
+            Transform::from_scale_angle_translation([2.0, 3.0], 0.5, [0.0, 0.0]),
+        );
+        let properties = shape.mass_properties(1.0);
+        let area = consts::PI * 6.0;
+        assert_abs_diff_eq!(properties.area, area, epsilon = 1e-4);
+        assert_abs_diff_eq!(properties.inertia, area * 13.0 / 4.0, epsilon = 1e-3);
+    }
+}
@@ -1,5 +1,12 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::generic::Real;
+
 mod array;
 mod glam;
+mod vector;
+
+pub(crate) use vector::Vector;
 
 pub(crate) trait Dot {
     type Output;
@@ -28,6 +35,35 @@ pub(crate) trait CmpToZero: Copy {
     fn is_positive(self) -> bool;
 }
 
+pub(crate) trait Scalar:
+    CmpToZero
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+}
+
 pub(crate) trait MagnitudeSquared {
     type Scalar;
     fn magnitude_squared(self) -> Self::Scalar;
@@ -54,6 +90,30 @@ impl CmpToZero for f32 {
     }
 }
 
+impl Scalar for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+}
+
+impl<S: Real> CmpToZero for S {
+    fn is_negative(self) -> bool {
+        self < Real::ZERO
+    }
+
+    fn is_zero(self) -> bool {
+        self == Real::ZERO
+    }
+
+    fn is_positive(self) -> bool {
+        self > Real::ZERO
+    }
+}
+
+impl<S: Real> Scalar for S {
+    const ZERO: Self = <S as Real>::ZERO;
+    const ONE: Self = <S as Real>::ONE;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,103 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use super::{Cross, Dot, Normalize, Perp};
+use crate::v2::generic::Real;
+
+/// 2d vector of any scalar type
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub(crate) struct Vector<S> {
+    pub(crate) x: S,
+    pub(crate) y: S,
+}
+
+impl<S> Vector<S> {
+    pub(crate) fn new(x: S, y: S) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<S> From<[S; 2]> for Vector<S> {
+    fn from([x, y]: [S; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<S> From<Vector<S>> for [S; 2] {
+    fn from(Vector { x, y }: Vector<S>) -> Self {
+        [x, y]
+    }
+}
+
+impl<S: Copy + Add<Output = S> + Mul<Output = S>> Dot for Vector<S> {
+    type Output = S;
+    fn dot(self, other: Self) -> Self::Output {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<S: Copy + Sub<Output = S> + Mul<Output = S>> Cross for Vector<S> {
+    type Output = S;
+    fn cross(self, other: Self) -> Self::Output {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<S: Neg<Output = S>> Perp for Vector<S> {
+    fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+impl<S: Real> Normalize for Vector<S> {
+    fn normalize(self) -> Option<Self> {
+        let length_squared = self.dot(self);
+        let inverse_length = S::ONE / length_squared.sqrt();
+        (length_squared > S::ZERO && inverse_length.is_finite() && inverse_length > S::ZERO)
+            .then(|| self * inverse_length)
+    }
+}
+
+impl<S: Add<Output = S>> Add for Vector<S> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<S: Sub<Output = S>> Sub for Vector<S> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<S: Neg<Output = S>> Neg for Vector<S> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<S: Copy + Mul<Output = S>> Mul<S> for Vector<S> {
+    type Output = Self;
+    fn mul(self, rhs: S) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_ulps_eq;
+
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        let [x, y]: [f64; 2] = Vector::new(3.0, -4.0).normalize().unwrap().into();
+        assert_ulps_eq!(x, 0.6);
+        assert_ulps_eq!(y, -0.8);
+        assert_eq!(Vector::new(0.0, 0.0).normalize(), None);
+        assert_eq!(Vector::new(f64::NAN, 0.0).normalize(), None);
+        assert_eq!(Vector::new(f64::INFINITY, 0.0).normalize(), None);
+    }
+}
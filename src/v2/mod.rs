@@ -1,22 +1,74 @@
+use core::f32::consts;
+
+use glam::Vec2;
+
+mod analytic;
+mod batch;
 mod broad_phase_interop;
+mod cache;
+mod compound;
+mod config;
+#[cfg(feature = "debug-svg")]
+mod debug_svg;
+mod decompose;
+mod encoding;
 mod epa;
+mod error;
+mod feature;
+pub mod generic;
+#[cfg(feature = "geo")]
+mod geo_interop;
 mod gjk;
+mod intersection;
+mod mass;
 mod math;
 mod minkowski;
+mod outline;
 #[cfg(test)]
 mod ray;
+#[cfg(feature = "serde")]
+mod serde_interop;
 pub mod shapes;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "tiled")]
+pub mod tiled;
 mod transform;
 
+use analytic::ClosedForm;
+pub use cache::PairCache;
+pub use compound::CompoundShape;
+pub use config::QueryConfig;
+#[cfg(feature = "debug-svg")]
+pub use debug_svg::DebugScene;
+pub use encoding::Quantization;
+pub use error::{DecodeError, QueryError, TransformError};
+pub use feature::Feature;
+use feature::SupportPoint;
+pub use intersection::Intersection;
+pub use mass::MassProperties;
 use shapes::ShapeData;
 pub use transform::Transform;
 
+/// Collision shape using `f64`, see [`generic`]
+pub type DCollisionShape = generic::CollisionShape<f64>;
+
+/// Transform of a [`DCollisionShape`], using `f64`
+pub type DTransform = generic::Transform<f64>;
+
+/// Collision shape using the deterministic [`generic::Fixed`] point numbers, see [`generic`]
+pub type FixedCollisionShape = generic::CollisionShape<generic::Fixed>;
+
+/// Transform of a [`FixedCollisionShape`], using [`generic::Fixed`] point numbers
+pub type FixedTransform = generic::Transform<generic::Fixed>;
+
 /// A collision shape
 ///
 /// This is the entry point for collision detection.
 ///
 /// See [crate](crate) level documentation for more info and examples.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollisionShape {
     transform: Transform,
     data: ShapeData,
@@ -57,6 +109,23 @@ impl CollisionShape {
         shapes::Segment::new(p1, p2).into()
     }
 
+    /// Create the smallest convex polygon containing all the `points`
+    ///
+    /// See [`ConvexPolygon::new`](shapes::ConvexPolygon::new)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::CollisionShape;
+    /// let triangle = CollisionShape::new_convex_polygon([[0.0, 0.0], [2.0, 0.0], [0.0, 2.0]]);
+    /// assert!(triangle.is_collided_with(&CollisionShape::new_circle(0.5)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new_convex_polygon<P: Into<[f32; 2]>>(points: impl IntoIterator<Item = P>) -> Self {
+        shapes::ConvexPolygon::new(points).into()
+    }
+
     /// Set the transform (translation, rotation and scale)
     ///
     /// This is equivalent to [`set_transform`](Self::set_transform), but in a builder style,
@@ -77,12 +146,23 @@ impl CollisionShape {
     /// Returns true if the two convex shapes geometries are overlapping
     #[must_use]
     pub fn is_collided_with(&self, other: &Self) -> bool {
-        let difference = minkowski::Difference {
-            shape1: self,
-            shape2: other,
-        };
-        let initial_axis = other.transform.position() - self.transform.position();
-        gjk::find_simplex_enclosing_origin(&difference, initial_axis).is_some()
+        self.is_collided_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`is_collided_with`](Self::is_collided_with), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn is_collided_with_config(&self, other: &Self, config: &QueryConfig) -> bool {
+        match self.closed_form_contact(other) {
+            ClosedForm::Unsupported => self
+                .find_simplex_enclosing_origin(
+                    other,
+                    self.initial_axis(other),
+                    config.max_gjk_iterations,
+                )
+                .is_ok(),
+            ClosedForm::Separated => false,
+            ClosedForm::Contact(_) => true,
+        }
     }
 
     /// Returns contact data with the other shape if they collide. Returns `None` if they don't collide.
@@ -92,20 +172,167 @@ impl CollisionShape {
     /// the two shapes will no longer be inter-penetrating.
     #[must_use]
     pub fn contact_with(&self, other: &Self) -> Option<Contact> {
+        self.contact_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`contact_with`](Self::contact_with), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn contact_with_config(&self, other: &Self, config: &QueryConfig) -> Option<Contact> {
+        match self.closed_form_contact(other) {
+            ClosedForm::Unsupported => {
+                self.contact_with_cached(other, &mut PairCache::with_config(*config))
+            }
+            ClosedForm::Separated => None,
+            ClosedForm::Contact(contact) => Some(contact),
+        }
+    }
+
+    /// Returns contact data with the other shape if they collide, constrained to a direction of resolution.
+    /// Returns `None` if they don't collide.
+    ///
+    /// The normal of the contact is always equal to the given `direction` (normalized),
+    /// and the penetration is the minimum translation along that direction to separate the shapes.
+    /// This is useful, for instance, to resolve collisions of a character standing on a slope only upward.
+    ///
+    /// If the `direction` is zero (or not finite), this is equivalent to [`contact_with`](Self::contact_with).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_abs_diff_eq;
+    /// use impacted::{CollisionShape, Transform};
+    /// let character = CollisionShape::new_rectangle(1.0, 1.0);
+    /// let slope = CollisionShape::new_rectangle(4.0, 4.0)
+    ///     .with_transform(Transform::from_angle_translation(0.3, [0.0, -2.3]));
+    /// let contact = character.contact_along(&slope, [0.0, 1.0]).unwrap();
+    /// assert_abs_diff_eq!(contact.normal[0], 0.0);
+    /// assert_abs_diff_eq!(contact.normal[1], 1.0);
+    /// ```
+    #[must_use]
+    pub fn contact_along(&self, other: &Self, direction: impl Into<[f32; 2]>) -> Option<Contact> {
+        self.contact_along_with_config(other, direction, &QueryConfig::default())
+    }
+
+    /// Same as [`contact_along`](Self::contact_along), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn contact_along_with_config(
+        &self,
+        other: &Self,
+        direction: impl Into<[f32; 2]>,
+        config: &QueryConfig,
+    ) -> Option<Contact> {
+        self.contact_within_cone_with_config(other, direction, 0.0, config)
+    }
+
+    /// Returns contact data with the other shape if they collide, constrained to a cone of resolution directions.
+    /// Returns `None` if they don't collide.
+    ///
+    /// The normal of the contact is within `max_angle` (in radians) of the given `direction`,
+    /// and the penetration is the minimum translation within that cone to separate the shapes.
+    ///
+    /// The `max_angle` is clamped between `0` (the normal is equal to the direction) and `π/2`
+    /// (the normal is in the half-plane of the direction).
+    ///
+    /// If the `direction` is zero (or not finite), this is equivalent to [`contact_with`](Self::contact_with).
+    #[must_use]
+    pub fn contact_within_cone(
+        &self,
+        other: &Self,
+        direction: impl Into<[f32; 2]>,
+        max_angle: f32,
+    ) -> Option<Contact> {
+        self.contact_within_cone_with_config(other, direction, max_angle, &QueryConfig::default())
+    }
+
+    /// Same as [`contact_within_cone`](Self::contact_within_cone), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn contact_within_cone_with_config(
+        &self,
+        other: &Self,
+        direction: impl Into<[f32; 2]>,
+        max_angle: f32,
+        config: &QueryConfig,
+    ) -> Option<Contact> {
+        let Some(direction) = Vec2::from(direction.into()).try_normalize() else {
+            return self.contact_with_config(other, config);
+        };
         let difference = minkowski::Difference {
             shape1: self,
             shape2: other,
         };
-        let initial_axis = other.transform.position() - self.transform.position();
-        let simplex = gjk::find_simplex_enclosing_origin(&difference, initial_axis)?;
-        let Contact {
-            normal,
-            penetration,
-        } = epa::generate_contact(&difference, simplex);
-        Some(Contact::<f32, [f32; 2]> {
-            normal: normal.into(),
-            penetration,
-        })
+        let initial_axis = self.initial_axis(other);
+        let simplex = gjk::find_simplex_enclosing_origin(
+            &difference,
+            SupportPoint::from(initial_axis),
+            config.max_gjk_iterations,
+        )
+        .ok()?;
+        let axis = SupportPoint::from(-direction);
+        let rotation = Vec2::from_angle(max_angle.clamp(0.0, consts::FRAC_PI_2));
+        let cone = epa::Cone {
+            axis,
+            left: rotation.rotate(axis.point).into(),
+            right: Vec2::new(rotation.x, -rotation.y).rotate(axis.point).into(),
+        };
+        let penetration = epa::generate_contact_in_cone(&difference, simplex, &cone, config);
+        Some(self.contact(other, penetration))
+    }
+
+    /// Returns the signed distance between the two shapes, and the axis along which they are separated
+    ///
+    /// The distance is positive if the shapes are apart, and negative if they are inter-penetrating.
+    /// In the later case, the separation is the opposite of the [`contact`](Self::contact_with) penetration.
+    ///
+    /// The normal is pointing toward this shape. In other words, moving this shape along the normal increases the distance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_ulps_eq;
+    /// use impacted::{CollisionShape, Transform};
+    /// let shape1 = CollisionShape::new_circle(1.0);
+    /// let shape2 = CollisionShape::new_circle(1.0).with_transform(Transform::from_translation([3.0, 0.0]));
+    /// let separation = shape1.separation(&shape2);
+    /// assert_ulps_eq!(separation.distance, 1.0);
+    /// assert_ulps_eq!(separation.normal[0], -1.0);
+    /// ```
+    #[must_use]
+    pub fn separation(&self, other: &Self) -> Separation {
+        self.separation_with_config(other, &QueryConfig::default())
+    }
+
+    /// Same as [`separation`](Self::separation), with the given tolerance and iteration limits
+    #[must_use]
+    pub fn separation_with_config(&self, other: &Self, config: &QueryConfig) -> Separation {
+        let difference = minkowski::Difference {
+            shape1: self,
+            shape2: other,
+        };
+        let initial_axis = self.initial_axis(other);
+        match gjk::find_closest_point_to_origin(
+            &difference,
+            initial_axis,
+            config.max_gjk_iterations,
+        ) {
+            Ok(closest) => Separation {
+                distance: closest.length(),
+                normal: closest.normalize_or_zero().into(),
+            },
+            Err(simplex) => {
+                let epa::Penetration { normal, depth, .. } =
+                    epa::generate_contact(&difference, simplex, config);
+                Separation {
+                    distance: -depth,
+                    normal: normal.into(),
+                }
+            }
+        }
+    }
+
+    /// Returns the transform of the collider
+    #[must_use]
+    pub fn transform(&self) -> &Transform {
+        &self.transform
     }
 
     /// Returns the shape data of the collider
@@ -113,6 +340,35 @@ impl CollisionShape {
     pub fn shape_data(&self) -> &ShapeData {
         &self.data
     }
+
+    /// Returns the direction in which to start the search of the separating axis with `other`
+    ///
+    /// The support point in a zero direction may be inside the minkowski difference (and break EPA),
+    /// so concentric shapes start from the x axis.
+    fn initial_axis(&self, other: &Self) -> Vec2 {
+        let offset = other.transform.position() - self.transform.position();
+        if offset == Vec2::ZERO {
+            Vec2::X
+        } else {
+            offset
+        }
+    }
+
+    /// Runs GJK on the Minkowski difference of the two shapes, starting from the given `axis`
+    ///
+    /// This only tells whether the shapes overlap, without generating a contact.
+    fn find_simplex_enclosing_origin(
+        &self,
+        other: &Self,
+        axis: Vec2,
+        max_iterations: usize,
+    ) -> Result<gjk::Simplex<Vec2>, gjk::NotEnclosed<Vec2>> {
+        let difference = minkowski::Difference {
+            shape1: self,
+            shape2: other,
+        };
+        gjk::find_simplex_enclosing_origin(&difference, axis, max_iterations)
+    }
 }
 
 /// Contact data between two shapes
@@ -120,6 +376,7 @@ impl CollisionShape {
 /// See [`CollisionShape::contact_with`]
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Contact<S = f32, V = [S; 2]> {
     /// Contact normal
     ///
@@ -130,6 +387,28 @@ pub struct Contact<S = f32, V = [S; 2]> {
     ///
     /// This is "how much" the two shapes are inter-penetrating
     pub penetration: S,
+    /// Features of the first and second shape that are in contact
+    ///
+    /// These identify the contact across frames, and can be used to match it with the contact of the previous frame.
+    /// See [`Feature`]
+    pub features: [Feature; 2],
+}
+
+/// Signed distance between two shapes
+///
+/// See [`CollisionShape::separation`]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Separation<S = f32, V = [S; 2]> {
+    /// Separation normal
+    ///
+    /// This is the direction on which the first shape should be moved to increase the distance between the shapes
+    pub normal: V,
+    /// Signed distance
+    ///
+    /// Positive if the shapes are apart, negative if they are inter-penetrating
+    pub distance: S,
 }
 
 trait Support<V> {
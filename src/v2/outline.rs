@@ -0,0 +1,155 @@
+use alloc::vec::Vec;
+use core::f32::consts;
+
+use glam::Vec2;
+
+use super::{
+    shapes::{Circle, ConvexPolygon, Rectangle, Segment, ShapeData},
+    CollisionShape,
+};
+
+/// Maximum number of vertices used to approximate a circle
+const MAX_CIRCLE_VERTICES: u16 = 1024;
+
+impl CollisionShape {
+    /// Returns the vertices of the shape in world space (with the transform applied), in counter-clockwise order
+    ///
+    /// This is a polygon approximating the shape, for drawing it. (To draw a closed line, repeat the first vertex at the end.)
+    ///
+    /// * Circles are subdivided so that no point of the polygon is further than `tolerance` from the actual circle.
+    ///   (The number of vertices per circle is limited to 1024, whatever the tolerance.)
+    /// * Rectangles and convex polygons have their exact vertices
+    /// * Segments have their two end points
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::{CollisionShape, Transform};
+    /// let rect = CollisionShape::new_rectangle(2.0, 4.0)
+    ///     .with_transform(Transform::from_translation([1.0, 0.0]));
+    /// let outline: Vec<[f32; 2]> = rect.outline(0.1).collect();
+    /// assert_eq!(outline, [[0.0, -2.0], [2.0, -2.0], [2.0, 2.0], [0.0, 2.0]]);
+    ///
+    /// let circle = CollisionShape::new_circle(10.0);
+    /// assert_eq!(circle.outline(0.1).len(), 32);
+    /// ```
+    #[must_use]
+    pub fn outline(&self, tolerance: f32) -> impl ExactSizeIterator<Item = [f32; 2]> {
+        self.world_outline(tolerance).into_iter().map(Into::into)
+    }
+
+    /// Same as [`outline`](Self::outline), collected in a vector
+    pub(crate) fn world_outline(&self, tolerance: f32) -> Vec<Vec2> {
+        let transform = self.transform.local_to_world();
+        let scale = transform
+            .matrix2
+            .x_axis
+            .length()
+            .max(transform.matrix2.y_axis.length());
+        let mut outline: Vec<Vec2> = self
+            .data
+            .outline(tolerance / scale)
+            .into_iter()
+            .map(|point| transform.transform_point2(point))
+            .collect();
+        if transform.matrix2.determinant() < 0.0 {
+            outline.reverse();
+        }
+        outline
+    }
+}
+
+impl Circle {
+    fn outline(self, tolerance: f32) -> Vec<Vec2> {
+        let mut count: u16 = 8;
+        while count < MAX_CIRCLE_VERTICES
+            && self.radius() * (1.0 - Vec2::from_angle(consts::PI / f32::from(count)).x) > tolerance
+        {
+            count *= 2;
+        }
+        let rotation = Vec2::from_angle(consts::TAU / f32::from(count));
+        let mut point = Vec2::new(self.radius(), 0.0);
+        (0..count)
+            .map(|_| {
+                let current = point;
+                point = rotation.rotate(point);
+                current
+            })
+            .collect()
+    }
+}
+
+impl Rectangle {
+    fn outline(self) -> Vec<Vec2> {
+        let [x, y] = self.half_extents();
+        [
+            Vec2::new(-x, -y),
+            Vec2::new(x, -y),
+            Vec2::new(x, y),
+            Vec2::new(-x, y),
+        ]
+        .into()
+    }
+}
+
+impl Segment {
+    fn outline(&self) -> Vec<Vec2> {
+        self.points().into_iter().map(Vec2::from).collect()
+    }
+}
+
+impl ConvexPolygon {
+    fn outline(&self) -> Vec<Vec2> {
+        self.vertices_slice().to_vec()
+    }
+}
+
+impl ShapeData {
+    /// Returns the vertices of the shape (in local space and counter-clockwise order)
+    fn outline(&self, tolerance: f32) -> Vec<Vec2> {
+        match self {
+            ShapeData::Circle(circle) => circle.outline(tolerance),
+            ShapeData::Rectangle(rect) => rect.outline(),
+            ShapeData::Segment(segment) => segment.outline(),
+            ShapeData::ConvexPolygon(polygon) => polygon.outline(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use rstest::rstest;
+
+    use crate::Transform;
+
+    use super::*;
+
+    #[rstest]
+    #[case(1.0, 0.1)]
+    #[case(1.0, 0.001)]
+    #[case(100.0, 0.5)]
+    fn circle_outline_is_within_tolerance(#[case] radius: f32, #[case] tolerance: f32) {
+        let circle = CollisionShape::new_circle(radius)
+            .with_transform(Transform::from_translation([3.0, -2.0]));
+        let outline: Vec<Vec2> = circle.outline(tolerance).map(Vec2::from).collect();
+        let center = Vec2::new(3.0, -2.0);
+        for (index, &point) in outline.iter().enumerate() {
+            assert_abs_diff_eq!(point.distance(center), radius, epsilon = radius * 1e-5);
+            let next = outline[(index + 1) % outline.len()];
+            let middle = (point + next) * 0.5;
+            assert!(radius - middle.distance(center) <= tolerance);
+        }
+    }
+
+    #[test]
+    fn outline_applies_the_transform() {
+        let shape = CollisionShape::new_segment([0.0, 0.0], [1.0, 0.0]).with_transform(
+            Transform::from_scale_angle_translation([2.0, 1.0], consts::FRAC_PI_2, [1.0, 1.0]),
+        );
+        let outline: Vec<Vec2> = shape.outline(0.1).map(Vec2::from).collect();
+        assert_eq!(outline.len(), 2);
+        assert_abs_diff_eq!(outline[0], Vec2::new(1.0, 1.0));
+        assert_abs_diff_eq!(outline[1], Vec2::new(1.0, 3.0), epsilon = 1e-6);
+    }
+}
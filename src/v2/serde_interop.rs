@@ -0,0 +1,161 @@
+//! Serialization of the types that can't derive it
+//!
+//! The transform only serializes its affine transformation (the inverse is computed on deserialization),
+//! and the shapes are deserialized through their constructors so that they stay valid
+//! (a convex polygon stays convex, and all the dimensions and coordinates are finite).
+
+use alloc::vec::Vec;
+
+use serde::{
+    de::{Error, Unexpected},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{
+    shapes::{Circle, ConvexPolygon, Rectangle, Segment},
+    Transform,
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Transform")]
+struct TransformData {
+    x_axis: [f32; 2],
+    y_axis: [f32; 2],
+    translation: [f32; 2],
+}
+
+impl Serialize for Transform {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let affine = self.local_to_world();
+        TransformData {
+            x_axis: affine.x_axis.into(),
+            y_axis: affine.y_axis.into(),
+            translation: affine.translation.into(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Transform {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = TransformData::deserialize(deserializer)?;
+        Transform::try_from_affine(data.x_axis, data.y_axis, data.translation)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Circle")]
+struct CircleData {
+    radius: f32,
+}
+
+impl Serialize for Circle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CircleData {
+            radius: self.radius(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Circle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let CircleData { radius } = CircleData::deserialize(deserializer)?;
+        if radius.is_finite() && radius >= 0.0 {
+            Ok(Circle::new(radius))
+        } else {
+            Err(D::Error::invalid_value(
+                Unexpected::Float(radius.into()),
+                &"a finite and non-negative radius",
+            ))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Rectangle")]
+struct RectangleData {
+    width: f32,
+    height: f32,
+}
+
+impl Serialize for Rectangle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [half_width, half_height] = self.half_extents();
+        RectangleData {
+            width: half_width * 2.0,
+            height: half_height * 2.0,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rectangle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let RectangleData { width, height } = RectangleData::deserialize(deserializer)?;
+        match [width, height].into_iter().find(|value| !value.is_finite()) {
+            None => Ok(Rectangle::new(width, height)),
+            Some(value) => Err(D::Error::invalid_value(
+                Unexpected::Float(value.into()),
+                &"a finite width and height",
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Segment")]
+struct SegmentData {
+    p1: [f32; 2],
+    p2: [f32; 2],
+}
+
+impl Serialize for Segment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [p1, p2] = self.points();
+        SegmentData { p1, p2 }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Segment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SegmentData { p1, p2 } = SegmentData::deserialize(deserializer)?;
+        check_finite_points(&[p1, p2])?;
+        Ok(Segment::new(p1, p2))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "ConvexPolygon")]
+struct ConvexPolygonData {
+    vertices: Vec<[f32; 2]>,
+}
+
+impl Serialize for ConvexPolygon {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ConvexPolygonData {
+            vertices: self.vertices().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConvexPolygon {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ConvexPolygonData::deserialize(deserializer)?;
+        check_finite_points(&data.vertices)?;
+        Ok(ConvexPolygon::new(data.vertices))
+    }
+}
+
+/// Returns an error if a coordinate of the `points` is not finite
+fn check_finite_points<E: Error>(points: &[[f32; 2]]) -> Result<(), E> {
+    match points.iter().flatten().find(|value| !value.is_finite()) {
+        None => Ok(()),
+        Some(&value) => Err(E::invalid_value(
+            Unexpected::Float(value.into()),
+            &"finite coordinates",
+        )),
+    }
+}
@@ -1,5 +1,7 @@
 //! Collection of shape data that can be used to create a [`CollisionShape`](crate::CollisionShape)
 
+use alloc::vec::Vec;
+
 use glam::Vec2;
 
 use super::Support;
@@ -7,6 +9,7 @@ use super::Support;
 /// Geometric information about a shape
 #[non_exhaustive]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShapeData {
     /// A circle
     ///
@@ -20,6 +23,10 @@ pub enum ShapeData {
     ///
     /// See [`Segment`]
     Segment(Segment),
+    /// A convex polygon
+    ///
+    /// See [`ConvexPolygon`]
+    ConvexPolygon(ConvexPolygon),
 }
 
 impl Support<Vec2> for ShapeData {
@@ -28,6 +35,7 @@ impl Support<Vec2> for ShapeData {
             ShapeData::Circle(circle) => circle.support(direction),
             ShapeData::Rectangle(rect) => rect.support(direction),
             ShapeData::Segment(segment) => segment.support(direction),
+            ShapeData::ConvexPolygon(polygon) => polygon.support(direction),
         }
     }
 }
@@ -131,6 +139,12 @@ impl Segment {
             p2: p2.into().into(),
         }
     }
+
+    /// Returns the two end points of the segment
+    #[must_use]
+    pub fn points(&self) -> [[f32; 2]; 2] {
+        [self.p1.into(), self.p2.into()]
+    }
 }
 
 impl From<Segment> for ShapeData {
@@ -149,6 +163,96 @@ impl Support<Vec2> for Segment {
     }
 }
 
+/// A convex polygon
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexPolygon {
+    /// Vertices in counter-clockwise order
+    vertices: Vec<Vec2>,
+}
+
+impl ConvexPolygon {
+    /// Creates the smallest convex polygon containing all the `points` (their convex hull)
+    ///
+    /// The points don't need to be ordered, and the points that are inside the hull (or on its edges) are ignored.
+    /// If all the points are aligned, the polygon is the segment joining the extreme points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::shapes::ConvexPolygon;
+    /// let triangle = ConvexPolygon::new([[0.0, 1.0], [0.0, 0.0], [0.2, 0.2], [1.0, 0.0]]);
+    /// assert_eq!(triangle.vertices().count(), 3);
+    /// ```
+    #[must_use]
+    pub fn new<P: Into<[f32; 2]>>(points: impl IntoIterator<Item = P>) -> Self {
+        let mut points: Vec<Vec2> = points.into_iter().map(|p| p.into().into()).collect();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+        if points.len() < 3 {
+            return Self { vertices: points };
+        }
+        // Andrew's monotone chain: the lower hull from left to right, then the upper hull from right to left
+        let mut vertices = half_hull(points.iter().copied());
+        vertices.extend(half_hull(points.iter().rev().copied()));
+        Self { vertices }
+    }
+
+    /// Returns the vertices of the polygon, in counter-clockwise order
+    #[must_use]
+    pub fn vertices(&self) -> impl ExactSizeIterator<Item = [f32; 2]> + '_ {
+        self.vertices.iter().map(|&v| v.into())
+    }
+
+    pub(crate) fn vertices_slice(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    /// Returns the index of the farthest vertex in the given direction
+    pub(crate) fn support_index(&self, direction: Vec2) -> usize {
+        let mut best = 0;
+        let mut best_dot = f32::NEG_INFINITY;
+        for (index, vertex) in self.vertices.iter().enumerate() {
+            let dot = vertex.dot(direction);
+            if dot > best_dot {
+                best = index;
+                best_dot = dot;
+            }
+        }
+        best
+    }
+}
+
+/// Returns the chain of counter-clockwise turns through the sorted `points`, without the last point
+fn half_hull(points: impl Iterator<Item = Vec2>) -> Vec<Vec2> {
+    let mut hull: Vec<Vec2> = Vec::new();
+    for point in points {
+        while let [.., a, b] = hull[..] {
+            if (b - a).perp_dot(point - b) > 0.0 {
+                break;
+            }
+            hull.pop();
+        }
+        hull.push(point);
+    }
+    hull.pop();
+    hull
+}
+
+impl From<ConvexPolygon> for ShapeData {
+    fn from(polygon: ConvexPolygon) -> Self {
+        Self::ConvexPolygon(polygon)
+    }
+}
+
+impl Support<Vec2> for ConvexPolygon {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.vertices
+            .get(self.support_index(direction))
+            .copied()
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,4 +303,34 @@ mod tests {
         assert_eq!(segment.support(-Vec2::X), Vec2::ZERO);
         assert_eq!(segment.support(-Vec2::X - Vec2::X), Vec2::ZERO);
     }
+
+    #[test]
+    fn convex_polygon_is_the_convex_hull() {
+        let polygon = ConvexPolygon::new([
+            [1.0, 1.0],
+            [0.0, 0.0],
+            [0.5, 0.5],
+            [1.0, 0.0],
+            [0.5, 0.0],
+            [0.0, 1.0],
+            [1.0, 1.0],
+        ]);
+        let vertices: Vec<[f32; 2]> = polygon.vertices().collect();
+        assert_eq!(vertices, [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+    }
+
+    #[test]
+    fn aligned_points_make_a_segment() {
+        let polygon = ConvexPolygon::new([[1.0, 1.0], [0.0, 0.0], [2.0, 2.0]]);
+        let vertices: Vec<[f32; 2]> = polygon.vertices().collect();
+        assert_eq!(vertices, [[0.0, 0.0], [2.0, 2.0]]);
+    }
+
+    #[test]
+    fn convex_polygon() {
+        let polygon = ConvexPolygon::new([[0.0, 0.0], [2.0, 0.0], [0.0, 1.0]]);
+        assert_eq!(polygon.support(Vec2::X), Vec2::new(2.0, 0.0));
+        assert_eq!(polygon.support(Vec2::Y), Vec2::new(0.0, 1.0));
+        assert_eq!(polygon.support(-Vec2::ONE), Vec2::ZERO);
+    }
 }
@@ -0,0 +1,517 @@
+//! Import of collision shapes from [SVG](https://www.w3.org/TR/SVG2/) documents
+//!
+//! See [`SvgDocument::parse`].
+//!
+//! Each `<rect>`, `<circle>`, `<ellipse>`, `<polygon>`, `<polyline>` and `<path>` element becomes a [`CompoundShape`]:
+//!
+//! * Rectangles become a [`Rectangle`](crate::shapes::Rectangle) (rounded corners are ignored)
+//! * Circles and ellipses become a [`Circle`](crate::shapes::Circle), scaled if the ellipse is not circular
+//! * Polygons become one or more [`ConvexPolygon`](crate::shapes::ConvexPolygon) (concave polygons are decomposed)
+//! * Polylines become one [`Segment`](crate::shapes::Segment) per line
+//! * In paths, each closed subpath (ending with `Z`) becomes a polygon, and each open subpath a polyline.
+//!   The curves are flattened so that no point of the outline is further than the given tolerance from the curve.
+//!   The subpaths are independent shapes: a subpath inside another is not a hole.
+//!
+//! The `transform` attributes of the elements and of their ancestors are applied (including skew).
+//! The content of `<defs>`, `<clipPath>`, `<mask>`, `<marker>`, `<pattern>` and `<symbol>` elements is ignored,
+//! as well as `<use>` elements and the elements of other namespaces (like the Inkscape metadata).
+//! Nested `<svg>` elements are ignored too, as their viewport (`x`, `y`, `width`, `height` and `viewBox`) is not supported.
+//! Elements with a size of zero (which are not rendered) are skipped.
+//!
+//! # Coordinates
+//!
+//! The shapes are expressed in the user units of the root `<svg>` element (the coordinates of its `viewBox`),
+//! with the y axis pointing down.
+//! Use [`SvgDocument::y_up_transform`] to convert the shapes to coordinates where the y axis points up.
+//!
+//! # Example
+//!
+//! ```
+//! use impacted::{svg::SvgDocument, CollisionShape, Transform};
+//! let document = SvgDocument::parse(r#"
+//!     <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 64 64">
+//!         <g transform="translate(32, 32)">
+//!             <circle id="head" cy="-16" r="8"/>
+//!             <path id="body" d="M -10 -8 L 10 -8 L 10 16 Q 0 24 -10 16 Z"/>
+//!         </g>
+//!     </svg>
+//! "#, 0.1).unwrap();
+//!
+//! assert_eq!(document.shapes.len(), 2);
+//! assert_eq!(document.shapes[0].id, "head");
+//! let point = CollisionShape::new_circle(0.0).with_transform(Transform::from_translation([32.0, 50.0]));
+//! assert!(document.shapes[1].shape.is_collided_with(&point));
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use glam::{Affine2, Vec2};
+use svgtypes::{Length, LengthUnit, SimplePathSegment, SimplifyingPathParser};
+
+use super::{CollisionShape, CompoundShape, Transform};
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Maximum number of lines a single curve is flattened into
+const MAX_CURVE_LINES: u32 = 1024;
+
+/// Collision shapes loaded from an SVG document
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct SvgDocument {
+    /// `viewBox` of the root element as `[min_x, min_y, width, height]`
+    ///
+    /// If the root element has no `viewBox`, it starts at the origin and has the `width` and `height` of the document.
+    pub view_box: [f32; 4],
+    /// Shapes of the document, in the order of the file
+    pub shapes: Vec<SvgShape>,
+}
+
+/// Shape element of an SVG document
+///
+/// See [`SvgDocument`]
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct SvgShape {
+    /// `id` attribute of the element (empty if there is none)
+    pub id: String,
+    /// Collision shape of the element, in the user units of the root element
+    ///
+    /// It contains a single shape, except for concave polygons, polylines and paths.
+    pub shape: CompoundShape,
+}
+
+/// Error returned when loading an SVG document
+///
+/// See [`SvgDocument::parse`]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SvgError {
+    /// The input is not valid XML
+    Syntax,
+    /// The input is not an SVG document, or has an invalid (or unsupported) attribute value
+    Invalid,
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::Syntax => f.write_str("syntax error in SVG document"),
+            SvgError::Invalid => f.write_str("invalid SVG document"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SvgError {}
+
+type Node<'a, 'input> = roxmltree::Node<'a, 'input>;
+
+impl SvgDocument {
+    /// Load the shape elements of an SVG document
+    ///
+    /// The curves of the paths are flattened with the given `tolerance`, in the user units of the root element.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`SvgError`] if the input is not a valid SVG document.
+    /// Lengths are only supported without unit or in pixels.
+    pub fn parse(source: &str, tolerance: f32) -> Result<Self, SvgError> {
+        let document = roxmltree::Document::parse(source).map_err(|_| SvgError::Syntax)?;
+        let root = document.root_element();
+        if !is_svg_element(root) || root.tag_name().name() != "svg" {
+            return Err(SvgError::Invalid);
+        }
+        let view_box = if let Some(view_box) = root.attribute("viewBox") {
+            let view_box = svgtypes::ViewBox::from_str(view_box).map_err(|_| SvgError::Invalid)?;
+            [view_box.x, view_box.y, view_box.w, view_box.h].map(narrow)
+        } else {
+            [
+                0.0,
+                0.0,
+                length(root, "width")?.unwrap_or(0.0),
+                length(root, "height")?.unwrap_or(0.0),
+            ]
+        };
+        let mut loader = Loader {
+            tolerance,
+            shapes: Vec::new(),
+        };
+        loader.load_children(root, Affine2::IDENTITY)?;
+        Ok(Self {
+            view_box,
+            shapes: loader.shapes,
+        })
+    }
+
+    /// Returns the transform converting the coordinates of the document (y down)
+    /// to coordinates with the y axis up and the origin at the bottom-left corner of the `viewBox`
+    ///
+    /// It mirrors the shapes, so that they look the same as in the document.
+    /// Compose it with the transform of each shape: `&document.y_up_transform() * shape.transform()`.
+    #[must_use]
+    pub fn y_up_transform(&self) -> Transform {
+        let [min_x, min_y, _, height] = self.view_box;
+        Transform::from_scale_angle_translation([1.0, -1.0], 0.0, [-min_x, min_y + height])
+    }
+}
+
+struct Loader {
+    tolerance: f32,
+    shapes: Vec<SvgShape>,
+}
+
+impl Loader {
+    fn load_children(&mut self, parent: Node<'_, '_>, transform: Affine2) -> Result<(), SvgError> {
+        for node in parent.children().filter(|node| is_svg_element(*node)) {
+            let transform = transform * element_transform(node)?;
+            let shape: CompoundShape = match node.tag_name().name() {
+                "g" | "a" | "switch" => {
+                    self.load_children(node, transform)?;
+                    continue;
+                }
+                "rect" => {
+                    let size = Vec2::new(number(node, "width")?, number(node, "height")?);
+                    if size.cmple(Vec2::ZERO).any() {
+                        continue;
+                    }
+                    let corner = Vec2::new(number(node, "x")?, number(node, "y")?);
+                    [
+                        CollisionShape::new_rectangle(size.x, size.y).with_transform(
+                            Transform::new(
+                                transform * Affine2::from_translation(corner + size * 0.5),
+                            ),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect()
+                }
+                "circle" => {
+                    let radius = number(node, "r")?;
+                    if radius <= 0.0 {
+                        continue;
+                    }
+                    let center = Vec2::new(number(node, "cx")?, number(node, "cy")?);
+                    [
+                        CollisionShape::new_circle(radius).with_transform(Transform::new(
+                            transform * Affine2::from_translation(center),
+                        )),
+                    ]
+                    .into_iter()
+                    .collect()
+                }
+                "ellipse" => {
+                    let (rx, ry) = (length(node, "rx")?, length(node, "ry")?);
+                    let radii = Vec2::new(rx.or(ry).unwrap_or(0.0), ry.or(rx).unwrap_or(0.0));
+                    if radii.cmple(Vec2::ZERO).any() {
+                        continue;
+                    }
+                    let center = Vec2::new(number(node, "cx")?, number(node, "cy")?);
+                    [
+                        CollisionShape::new_circle(1.0).with_transform(Transform::new(
+                            transform * Affine2::from_scale_angle_translation(radii, 0.0, center),
+                        )),
+                    ]
+                    .into_iter()
+                    .collect()
+                }
+                "polygon" => CompoundShape::new_polygon(points(node, transform)?),
+                "polyline" => polyline(&points(node, transform)?).collect(),
+                "path" => self.path(node, transform)?,
+                _ => continue,
+            };
+            self.shapes.push(SvgShape {
+                id: node.attribute("id").unwrap_or_default().to_string(),
+                shape,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the shapes of the subpaths of a `<path>` element
+    fn path(&self, node: Node<'_, '_>, transform: Affine2) -> Result<CompoundShape, SvgError> {
+        let point = |x: f64, y: f64| transform.transform_point2(Vec2::new(narrow(x), narrow(y)));
+        let mut shapes = Vec::new();
+        let mut subpath: Vec<Vec2> = Vec::new();
+        let mut start = Vec2::ZERO;
+        for segment in SimplifyingPathParser::from(node.attribute("d").unwrap_or_default()) {
+            let segment = segment.map_err(|_| SvgError::Invalid)?;
+            if subpath.is_empty() && !matches!(segment, SimplePathSegment::MoveTo { .. }) {
+                // A subpath closed by `Z` continues from its start
+                subpath.push(start);
+            }
+            let current = subpath.last().copied().unwrap_or(start);
+            match segment {
+                SimplePathSegment::MoveTo { x, y } => {
+                    shapes.extend(polyline(&subpath));
+                    subpath.clear();
+                    start = point(x, y);
+                    subpath.push(start);
+                }
+                SimplePathSegment::LineTo { x, y } => subpath.push(point(x, y)),
+                SimplePathSegment::Quadratic { x1, y1, x, y } => {
+                    let [p1, p2] = [point(x1, y1), point(x, y)];
+                    let lines =
+                        curve_lines(2.0 * (current - 2.0 * p1 + p2).length(), self.tolerance);
+                    subpath.extend((1..=lines).map(|i| {
+                        let t = step(i, lines);
+                        current.lerp(p1, t).lerp(p1.lerp(p2, t), t)
+                    }));
+                }
+                SimplePathSegment::CurveTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                } => {
+                    let [p1, p2, p3] = [point(x1, y1), point(x2, y2), point(x, y)];
+                    let second_derivative = (current - 2.0 * p1 + p2)
+                        .length()
+                        .max((p1 - 2.0 * p2 + p3).length());
+                    let lines = curve_lines(6.0 * second_derivative, self.tolerance);
+                    subpath.extend((1..=lines).map(|i| {
+                        let t = step(i, lines);
+                        let [q0, q1, q2] = [current.lerp(p1, t), p1.lerp(p2, t), p2.lerp(p3, t)];
+                        q0.lerp(q1, t).lerp(q1.lerp(q2, t), t)
+                    }));
+                }
+                SimplePathSegment::ClosePath if subpath.len() < 3 => {
+                    shapes.extend(polyline(&subpath));
+                    subpath.clear();
+                }
+                SimplePathSegment::ClosePath => {
+                    shapes.extend(CompoundShape::new_polygon(subpath.drain(..)));
+                }
+            }
+        }
+        shapes.extend(polyline(&subpath));
+        Ok(shapes.into())
+    }
+}
+
+/// Returns the number of lines needed to flatten a curve within `tolerance`,
+/// given an upper bound of the norm of its second derivative
+///
+/// The distance between a curve and its uniform subdivision in `n` lines is at most `bound / (8 * n²)`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn curve_lines(second_derivative_bound: f32, tolerance: f32) -> u32 {
+    let lines = libm::ceilf(libm::sqrtf(second_derivative_bound / (8.0 * tolerance)));
+    if lines.is_nan() {
+        return MAX_CURVE_LINES;
+    }
+    (lines as u32).clamp(1, MAX_CURVE_LINES)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn step(index: u32, count: u32) -> f32 {
+    index as f32 / count as f32
+}
+
+/// Returns one segment per line of the polyline
+fn polyline(points: &[Vec2]) -> impl Iterator<Item = CollisionShape> + '_ {
+    points
+        .windows(2)
+        .map(|line| CollisionShape::new_segment(line[0], line[1]))
+}
+
+fn is_svg_element(node: Node<'_, '_>) -> bool {
+    node.is_element() && matches!(node.tag_name().namespace(), None | Some(SVG_NAMESPACE))
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn narrow(value: f64) -> f32 {
+    value as f32
+}
+
+/// Returns the `transform` attribute of the element (identity if there is none)
+fn element_transform(node: Node<'_, '_>) -> Result<Affine2, SvgError> {
+    let Some(transform) = node.attribute("transform") else {
+        return Ok(Affine2::IDENTITY);
+    };
+    let t = svgtypes::Transform::from_str(transform).map_err(|_| SvgError::Invalid)?;
+    Ok(Affine2::from_cols_array(
+        &[t.a, t.b, t.c, t.d, t.e, t.f].map(narrow),
+    ))
+}
+
+/// Parses a length attribute (only user units and pixels are supported)
+fn length(node: Node<'_, '_>, name: &str) -> Result<Option<f32>, SvgError> {
+    let Some(value) = node.attribute(name) else {
+        return Ok(None);
+    };
+    match Length::from_str(value) {
+        Ok(Length {
+            number,
+            unit: LengthUnit::None | LengthUnit::Px,
+        }) => Ok(Some(narrow(number))),
+        _ => Err(SvgError::Invalid),
+    }
+}
+
+/// Parses a length attribute that is zero if absent
+fn number(node: Node<'_, '_>, name: &str) -> Result<f32, SvgError> {
+    Ok(length(node, name)?.unwrap_or(0.0))
+}
+
+/// Parses the `points` attribute, and transforms the points
+fn points(node: Node<'_, '_>, transform: Affine2) -> Result<Vec<Vec2>, SvgError> {
+    let source = node.attribute("points").unwrap_or_default();
+    let points: Vec<Vec2> = svgtypes::PointsParser::from(source)
+        .map(|(x, y)| transform.transform_point2(Vec2::new(narrow(x), narrow(y))))
+        .collect();
+    // The parser stops at the first invalid point
+    if points.is_empty() && !source.trim().is_empty() {
+        return Err(SvgError::Invalid);
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn area(shape: &CompoundShape) -> f32 {
+        shape
+            .shapes()
+            .iter()
+            .map(|shape| shape.mass_properties(1.0).area)
+            .sum()
+    }
+
+    fn probe(x: f32, y: f32) -> CollisionShape {
+        CollisionShape::new_circle(0.05).with_transform(Transform::from_translation([x, y]))
+    }
+
+    const DOCUMENT: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
+     width="200" height="100" viewBox="-10 -20 100 50">
+  <inkscape:namedview><rect width="1000" height="1000"/></inkscape:namedview>
+  <defs><rect id="hidden" width="10" height="10"/></defs>
+  <rect id="rect" x="10" y="20" width="4px" height="2" transform="rotate(90 10 20)"/>
+  <g transform="translate(100 0) scale(2)">
+    <circle id="circle" cx="5" cy="5" r="1"/>
+    <ellipse id="ellipse" cx="10" cy="5" rx="2" ry="1"/>
+    <polygon id="polygon" points="0,0 3,0 3,1 1,1 1,3 0,3"/>
+  </g>
+  <polyline id="polyline" points="0 0 10 0 10 10" transform="skewX(10)"/>
+  <circle r="0"/>
+  <use href="#hidden"/>
+  <svg x="50" width="10" height="10" viewBox="0 0 1 1"><rect id="nested" width="1" height="1"/></svg>
+</svg>"##;
+
+    #[test]
+    fn load_shapes() {
+        let document = SvgDocument::parse(DOCUMENT, 0.01).unwrap();
+        assert_abs_diff_eq!(
+            glam::Vec4::from(document.view_box),
+            glam::Vec4::new(-10.0, -20.0, 100.0, 50.0)
+        );
+        let ids: Vec<&str> = document.shapes.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["rect", "circle", "ellipse", "polygon", "polyline"]);
+
+        // Rotated by 90° (clockwise on screen) around its top-left corner
+        let rect = &document.shapes[0].shape;
+        assert_abs_diff_eq!(area(rect), 8.0, epsilon = 1e-4);
+        assert!(rect.is_collided_with(&probe(9.0, 23.9)));
+        assert!(!rect.is_collided_with(&probe(10.5, 22.0)));
+
+        let circle = &document.shapes[1].shape;
+        assert_abs_diff_eq!(area(circle), 4.0 * core::f32::consts::PI, epsilon = 1e-3);
+        assert!(circle.is_collided_with(&probe(110.0, 11.9)));
+
+        let ellipse = &document.shapes[2].shape;
+        assert!(ellipse.is_collided_with(&probe(123.9, 10.0)));
+        assert!(!ellipse.is_collided_with(&probe(120.0, 12.5)));
+
+        let polygon = &document.shapes[3].shape;
+        assert_eq!(polygon.shapes().len(), 2);
+        assert_abs_diff_eq!(area(polygon), 20.0, epsilon = 1e-3);
+        assert!(!polygon.is_collided_with(&probe(105.0, 5.0)));
+
+        let polyline = &document.shapes[4].shape;
+        assert_eq!(polyline.shapes().len(), 2);
+        let skew = libm::tanf(10_f32.to_radians()) * 10.0;
+        assert!(polyline.is_collided_with(&probe(10.0 + skew, 10.0)));
+    }
+
+    #[test]
+    fn path_curves_are_flattened_within_tolerance() {
+        let document = SvgDocument::parse(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+                <path d="M 10 0 A 10 10 0 0 1 -10 0 A 10 10 0 0 1 10 0 Z"/>
+                <path d="M 0 0 C 0 10 10 10 10 0 m 10 0 q 5 -10 10 0 z"/>
+            </svg>"#,
+            0.01,
+        )
+        .unwrap();
+
+        let circle = &document.shapes[0].shape;
+        let expected = core::f32::consts::PI * 100.0;
+        // The flattened outline is within the tolerance of the circle
+        assert_abs_diff_eq!(
+            area(circle),
+            expected,
+            epsilon = 0.01 * 2.0 * expected / 10.0
+        );
+        assert!(circle.is_collided_with(&probe(0.0, 9.9)));
+        assert!(circle.is_collided_with(&probe(7.0, -7.0)));
+
+        let curves = &document.shapes[1].shape;
+        // The cubic curve is open: it only becomes segments, reaching y = 7.5 at its middle
+        assert!(curves.is_collided_with(&probe(5.0, 7.5)));
+        assert!(!curves.is_collided_with(&probe(5.0, 5.0)));
+        // The quadratic curve is closed: a filled polygon (area = 2/3 * 10 * 5)
+        let filled = curves
+            .shapes()
+            .iter()
+            .filter(|s| s.mass_properties(1.0).area > 0.0);
+        let filled_area: f32 = filled.map(|s| s.mass_properties(1.0).area).sum();
+        assert_abs_diff_eq!(filled_area, 100.0 / 3.0, epsilon = 0.1);
+        assert!(curves.is_collided_with(&probe(25.0, -4.0)));
+    }
+
+    #[test]
+    fn y_up_transform() {
+        let document = SvgDocument::parse(DOCUMENT, 0.01).unwrap();
+        let circle = &document.shapes[1].shape.shapes()[0];
+        let circle = circle
+            .clone()
+            .with_transform(&document.y_up_transform() * circle.transform());
+        assert_abs_diff_eq!(
+            Vec2::from(circle.mass_properties(1.0).centroid),
+            Vec2::new(120.0, 30.0 - 10.0),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let parse = |source| SvgDocument::parse(source, 0.1).unwrap_err();
+        assert_eq!(parse("<svg"), SvgError::Syntax);
+        assert_eq!(parse("<map/>"), SvgError::Invalid);
+        assert_eq!(
+            parse(r#"<svg><rect width="10%" height="1"/></svg>"#),
+            SvgError::Invalid
+        );
+        assert_eq!(
+            parse(r#"<svg><path transform="rotate(a)"/></svg>"#),
+            SvgError::Invalid
+        );
+        assert_eq!(
+            parse(r#"<svg><path d="M 0 0 X"/></svg>"#),
+            SvgError::Invalid
+        );
+        assert_eq!(
+            parse(r#"<svg><polygon points="a"/></svg>"#),
+            SvgError::Invalid
+        );
+    }
+}
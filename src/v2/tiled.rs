@@ -0,0 +1,629 @@
+//! Import of collision shapes from the object layers of [Tiled](https://www.mapeditor.org) maps
+//!
+//! Both the XML (`.tmx`) and JSON (`.tmj`) formats are supported, see [`TiledMap::from_tmx`] and [`TiledMap::from_tmj`].
+//!
+//! Each object of the object layers becomes a [`CompoundShape`], positioned like in Tiled:
+//!
+//! * Rectangles become a [`Rectangle`](crate::shapes::Rectangle) (and tile objects the rectangle covered by the tile)
+//! * Ellipses become a [`Circle`](crate::shapes::Circle), scaled if the ellipse is not circular
+//! * Points become a circle with a radius of zero
+//! * Polygons become one or more [`ConvexPolygon`](crate::shapes::ConvexPolygon) (concave polygons are decomposed)
+//! * Polylines become one [`Segment`](crate::shapes::Segment) per line
+//!
+//! Text objects are ignored. Objects that use a template only get the attributes overridden in the map
+//! (the templates are not loaded).
+//!
+//! # Coordinates
+//!
+//! The shapes are expressed in the coordinates of the map: in pixels, with the origin at the top-left corner
+//! of the map, and the y axis pointing down.
+//! The objects are rotated around their origin (which is the top-left corner of the rectangles and ellipses,
+//! and the bottom-left corner of the tile objects), and the offsets of the layers and groups are applied.
+//!
+//! Use [`TiledMap::y_up_transform`] to convert the shapes to coordinates where the y axis points up.
+//!
+//! # Example
+//!
+//! ```
+//! use impacted::{tiled::TiledMap, CollisionShape};
+//! let map = TiledMap::from_tmx(r#"
+//!     <map version="1.10" orientation="orthogonal" width="10" height="10" tilewidth="16" tileheight="16">
+//!         <objectgroup id="1" name="walls">
+//!             <object id="1" name="floor" x="0" y="144" width="160" height="16"/>
+//!         </objectgroup>
+//!     </map>
+//! "#).unwrap();
+//!
+//! let to_y_up = map.y_up_transform();
+//! let floor: Vec<CollisionShape> = map.objects[0].shape.shapes().iter()
+//!     .map(|shape| shape.clone().with_transform(&to_y_up * shape.transform()))
+//!     .collect();
+//! assert_eq!(floor[0].transform().translation(), [80.0, 8.0]);
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use glam::Vec2;
+
+use super::{CollisionShape, CompoundShape, Transform};
+
+/// Collision shapes loaded from a Tiled map
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct TiledMap {
+    /// Size of the map in pixels (the number of tiles multiplied by the size of the tiles)
+    pub size: [f32; 2],
+    /// Objects of all the object layers, in the order of the file
+    pub objects: Vec<TiledObject>,
+}
+
+/// Object of a Tiled object layer
+///
+/// See [`TiledMap`]
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct TiledObject {
+    /// Unique identifier of the object in the map
+    pub id: u32,
+    /// Name of the object
+    pub name: String,
+    /// Class (formerly type) of the object
+    pub class: String,
+    /// Name of the object layer containing the object
+    pub layer: String,
+    /// Collision shape of the object, in the coordinates of the map
+    ///
+    /// It contains a single shape, except for concave polygons and polylines.
+    pub shape: CompoundShape,
+}
+
+/// Error returned when loading a Tiled map
+///
+/// See [`TiledMap::from_tmx`] and [`TiledMap::from_tmj`]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TiledError {
+    /// The input is not valid XML or JSON
+    Syntax,
+    /// The input is not a Tiled map, or has an invalid value
+    Invalid,
+}
+
+impl fmt::Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TiledError::Syntax => f.write_str("syntax error in Tiled map"),
+            TiledError::Invalid => f.write_str("invalid Tiled map"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TiledError {}
+
+impl TiledMap {
+    /// Load the object layers of a map in the XML format of Tiled (`.tmx`)
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TiledError`] if the input is not a valid Tiled map
+    pub fn from_tmx(source: &str) -> Result<Self, TiledError> {
+        let document = roxmltree::Document::parse(source).map_err(|_| TiledError::Syntax)?;
+        let root = document.root_element();
+        if root.tag_name().name() != "map" {
+            return Err(TiledError::Invalid);
+        }
+        let mut objects = Vec::new();
+        tmx::load_layers(root, Vec2::ZERO, &mut objects)?;
+        Ok(Self {
+            size: map_size(|name| tmx::number(root, name))?,
+            objects,
+        })
+    }
+
+    /// Load the object layers of a map in the JSON format of Tiled (`.tmj`)
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TiledError`] if the input is not a valid Tiled map
+    pub fn from_tmj(source: &str) -> Result<Self, TiledError> {
+        let root: serde_json::Value =
+            serde_json::from_str(source).map_err(|_| TiledError::Syntax)?;
+        if root.get("type").and_then(serde_json::Value::as_str) != Some("map") {
+            return Err(TiledError::Invalid);
+        }
+        let mut objects = Vec::new();
+        tmj::load_layers(&root, Vec2::ZERO, &mut objects)?;
+        Ok(Self {
+            size: map_size(|name| tmj::number(&root, name))?,
+            objects,
+        })
+    }
+
+    /// Returns the transform converting the coordinates of the map (y down, origin at the top-left corner)
+    /// to coordinates with the y axis up and the origin at the bottom-left corner of the map
+    ///
+    /// It mirrors the shapes, so that they look the same as in Tiled.
+    /// Compose it with the transform of each shape: `&map.y_up_transform() * shape.transform()`.
+    #[must_use]
+    pub fn y_up_transform(&self) -> Transform {
+        Transform::from_scale_angle_translation([1.0, -1.0], 0.0, [0.0, self.size[1]])
+    }
+}
+
+fn map_size(
+    mut number: impl FnMut(&str) -> Result<Option<f32>, TiledError>,
+) -> Result<[f32; 2], TiledError> {
+    let mut value = |name| Ok(number(name)?.unwrap_or(0.0));
+    Ok([
+        value("width")? * value("tilewidth")?,
+        value("height")? * value("tileheight")?,
+    ])
+}
+
+/// Geometry of an object, before conversion to collision shapes
+enum Geometry {
+    Rectangle,
+    Ellipse,
+    Point,
+    Polygon(Vec<Vec2>),
+    Polyline(Vec<Vec2>),
+    Tile,
+    Text,
+}
+
+/// Object read from the map, in either format
+struct RawObject {
+    id: u32,
+    name: String,
+    class: String,
+    position: Vec2,
+    size: Vec2,
+    rotation: f32,
+    geometry: Geometry,
+}
+
+impl RawObject {
+    /// Returns the object with its shape, or `None` if it has no shape (like text objects)
+    fn into_object(self, layer: &str, offset: Vec2) -> Option<TiledObject> {
+        let origin =
+            Transform::from_angle_translation(self.rotation.to_radians(), self.position + offset);
+        let positioned = |shape: CollisionShape, center: Vec2| {
+            let center = origin.transform_point(center);
+            shape.with_transform(Transform::from_angle_translation(
+                self.rotation.to_radians(),
+                center,
+            ))
+        };
+        let size = self.size;
+        let shape = match self.geometry {
+            Geometry::Rectangle => [positioned(
+                CollisionShape::new_rectangle(size.x, size.y),
+                size * 0.5,
+            )]
+            .into_iter()
+            .collect(),
+            Geometry::Tile => [positioned(
+                CollisionShape::new_rectangle(size.x, size.y),
+                Vec2::new(size.x, -size.y) * 0.5,
+            )]
+            .into_iter()
+            .collect(),
+            Geometry::Ellipse if (size.x - size.y).abs() <= f32::EPSILON * size.x => [positioned(
+                CollisionShape::new_circle(size.x * 0.5),
+                size * 0.5,
+            )]
+            .into_iter()
+            .collect(),
+            Geometry::Ellipse => {
+                let center = origin.transform_point(size * 0.5);
+                [CollisionShape::new_circle(0.5).with_transform(
+                    Transform::from_scale_angle_translation(
+                        size,
+                        self.rotation.to_radians(),
+                        center,
+                    ),
+                )]
+                .into_iter()
+                .collect()
+            }
+            Geometry::Point => [CollisionShape::new_circle(0.0)
+                .with_transform(Transform::from_translation(origin.translation()))]
+            .into_iter()
+            .collect(),
+            Geometry::Polygon(points) => CompoundShape::new_polygon(points)
+                .into_iter()
+                .map(|shape| shape.with_transform(origin.clone()))
+                .collect(),
+            Geometry::Polyline(points) => points
+                .windows(2)
+                .map(|line| {
+                    CollisionShape::new_segment(line[0], line[1]).with_transform(origin.clone())
+                })
+                .collect(),
+            Geometry::Text => return None,
+        };
+        Some(TiledObject {
+            id: self.id,
+            name: self.name,
+            class: self.class,
+            layer: layer.to_string(),
+            shape,
+        })
+    }
+}
+
+mod tmx {
+    use super::*;
+
+    type Node<'a, 'input> = roxmltree::Node<'a, 'input>;
+
+    pub(super) fn number(node: Node<'_, '_>, name: &str) -> Result<Option<f32>, TiledError> {
+        node.attribute(name)
+            .map(|value| value.trim().parse().map_err(|_| TiledError::Invalid))
+            .transpose()
+    }
+
+    fn layer_offset(node: Node<'_, '_>) -> Result<Vec2, TiledError> {
+        Ok(Vec2::new(
+            number(node, "offsetx")?.unwrap_or(0.0),
+            number(node, "offsety")?.unwrap_or(0.0),
+        ))
+    }
+
+    /// Loads the object layers that are children of `parent` (recursively for groups)
+    pub(super) fn load_layers(
+        parent: Node<'_, '_>,
+        offset: Vec2,
+        objects: &mut Vec<TiledObject>,
+    ) -> Result<(), TiledError> {
+        for layer in parent.children().filter(Node::is_element) {
+            match layer.tag_name().name() {
+                "group" => load_layers(layer, offset + layer_offset(layer)?, objects)?,
+                "objectgroup" => {
+                    let offset = offset + layer_offset(layer)?;
+                    let name = layer.attribute("name").unwrap_or_default();
+                    for object in layer.children().filter(|n| n.has_tag_name("object")) {
+                        objects.extend(load_object(object)?.into_object(name, offset));
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn load_object(object: Node<'_, '_>) -> Result<RawObject, TiledError> {
+        let mut geometry = if object.has_attribute("gid") {
+            Geometry::Tile
+        } else {
+            Geometry::Rectangle
+        };
+        for child in object.children().filter(Node::is_element) {
+            geometry = match child.tag_name().name() {
+                "ellipse" => Geometry::Ellipse,
+                "point" => Geometry::Point,
+                "polygon" => Geometry::Polygon(points(child)?),
+                "polyline" => Geometry::Polyline(points(child)?),
+                "text" => Geometry::Text,
+                _ => continue,
+            };
+        }
+        let number = |name| Ok::<_, TiledError>(number(object, name)?.unwrap_or(0.0));
+        Ok(RawObject {
+            id: object
+                .attribute("id")
+                .map_or(Ok(0), str::parse)
+                .map_err(|_| TiledError::Invalid)?,
+            name: object.attribute("name").unwrap_or_default().to_string(),
+            class: object
+                .attribute("type")
+                .or_else(|| object.attribute("class"))
+                .unwrap_or_default()
+                .to_string(),
+            position: Vec2::new(number("x")?, number("y")?),
+            size: Vec2::new(number("width")?, number("height")?),
+            rotation: number("rotation")?,
+            geometry,
+        })
+    }
+
+    /// Parses the `points` attribute (like `"0,0 10,5 3,-2"`)
+    fn points(node: Node<'_, '_>) -> Result<Vec<Vec2>, TiledError> {
+        node.attribute("points")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|point| {
+                let (x, y) = point.split_once(',').ok_or(TiledError::Invalid)?;
+                Ok(Vec2::new(
+                    x.parse().map_err(|_| TiledError::Invalid)?,
+                    y.parse().map_err(|_| TiledError::Invalid)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+mod tmj {
+    use serde_json::Value;
+
+    use super::*;
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub(super) fn number(value: &Value, name: &str) -> Result<Option<f32>, TiledError> {
+        value
+            .get(name)
+            .map(|value| value.as_f64().map(|v| v as f32).ok_or(TiledError::Invalid))
+            .transpose()
+    }
+
+    fn string<'a>(value: &'a Value, name: &str) -> &'a str {
+        value.get(name).and_then(Value::as_str).unwrap_or_default()
+    }
+
+    fn layer_offset(layer: &Value) -> Result<Vec2, TiledError> {
+        Ok(Vec2::new(
+            number(layer, "offsetx")?.unwrap_or(0.0),
+            number(layer, "offsety")?.unwrap_or(0.0),
+        ))
+    }
+
+    /// Loads the object layers of `parent` (recursively for groups)
+    pub(super) fn load_layers(
+        parent: &Value,
+        offset: Vec2,
+        objects: &mut Vec<TiledObject>,
+    ) -> Result<(), TiledError> {
+        let Some(layers) = parent.get("layers") else {
+            return Ok(());
+        };
+        for layer in layers.as_array().ok_or(TiledError::Invalid)? {
+            match string(layer, "type") {
+                "group" => load_layers(layer, offset + layer_offset(layer)?, objects)?,
+                "objectgroup" => {
+                    let offset = offset + layer_offset(layer)?;
+                    let name = string(layer, "name");
+                    let layer_objects = layer.get("objects").and_then(Value::as_array);
+                    for object in layer_objects.into_iter().flatten() {
+                        objects.extend(load_object(object)?.into_object(name, offset));
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn load_object(object: &Value) -> Result<RawObject, TiledError> {
+        let flag = |name| object.get(name).and_then(Value::as_bool) == Some(true);
+        let geometry = if let Some(points) = object.get("polygon") {
+            Geometry::Polygon(self::points(points)?)
+        } else if let Some(points) = object.get("polyline") {
+            Geometry::Polyline(self::points(points)?)
+        } else if flag("ellipse") {
+            Geometry::Ellipse
+        } else if flag("point") {
+            Geometry::Point
+        } else if object.get("text").is_some() {
+            Geometry::Text
+        } else if object.get("gid").is_some() {
+            Geometry::Tile
+        } else {
+            Geometry::Rectangle
+        };
+        let number = |name| Ok::<_, TiledError>(number(object, name)?.unwrap_or(0.0));
+        let class = match string(object, "type") {
+            "" => string(object, "class"),
+            class => class,
+        };
+        Ok(RawObject {
+            id: object
+                .get("id")
+                .map_or(Some(0), Value::as_u64)
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or(TiledError::Invalid)?,
+            name: string(object, "name").to_string(),
+            class: class.to_string(),
+            position: Vec2::new(number("x")?, number("y")?),
+            size: Vec2::new(number("width")?, number("height")?),
+            rotation: number("rotation")?,
+            geometry,
+        })
+    }
+
+    fn points(points: &Value) -> Result<Vec<Vec2>, TiledError> {
+        points
+            .as_array()
+            .ok_or(TiledError::Invalid)?
+            .iter()
+            .map(|point| {
+                Ok(Vec2::new(
+                    number(point, "x")?.ok_or(TiledError::Invalid)?,
+                    number(point, "y")?.ok_or(TiledError::Invalid)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    const TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" tiledversion="1.10.2" orientation="orthogonal" width="20" height="10" tilewidth="16" tileheight="16">
+ <layer id="1" name="ground" width="20" height="10"><data encoding="csv">0</data></layer>
+ <objectgroup id="2" name="walls" offsetx="5" offsety="-5">
+  <object id="1" name="wall" type="solid" x="10" y="20" width="4" height="2" rotation="90"/>
+  <object id="2" x="30" y="40" width="6" height="6">
+   <ellipse/>
+  </object>
+  <object id="3" x="30" y="40" width="6" height="2" rotation="-90">
+   <ellipse/>
+  </object>
+ </objectgroup>
+ <group id="3" name="group" offsetx="100">
+  <objectgroup id="4" name="nested">
+   <object id="4" name="spawn" x="1" y="2">
+    <point/>
+   </object>
+   <object id="5" x="50" y="50">
+    <polygon points="0,0 3,0 3,1 1,1 1,3 0,3"/>
+   </object>
+   <object id="6" x="50" y="50" rotation="45">
+    <polyline points="0,0 10,0 10,10"/>
+   </object>
+   <object id="7" gid="1" x="0" y="16" width="16" height="16"/>
+   <object id="8" x="0" y="0" width="100" height="20">
+    <text>Hello</text>
+   </object>
+  </objectgroup>
+ </group>
+</map>"#;
+
+    const TMJ: &str = r#"{
+ "type": "map", "orientation": "orthogonal", "width": 20, "height": 10, "tilewidth": 16, "tileheight": 16,
+ "layers": [
+  {"type": "tilelayer", "name": "ground", "data": [0]},
+  {"type": "objectgroup", "name": "walls", "offsetx": 5, "offsety": -5, "objects": [
+   {"id": 1, "name": "wall", "type": "solid", "x": 10, "y": 20, "width": 4, "height": 2, "rotation": 90},
+   {"id": 2, "name": "", "x": 30, "y": 40, "width": 6, "height": 6, "ellipse": true},
+   {"id": 3, "name": "", "x": 30, "y": 40, "width": 6, "height": 2, "rotation": -90, "ellipse": true}
+  ]},
+  {"type": "group", "name": "group", "offsetx": 100, "layers": [
+   {"type": "objectgroup", "name": "nested", "objects": [
+    {"id": 4, "name": "spawn", "x": 1, "y": 2, "point": true},
+    {"id": 5, "x": 50, "y": 50, "polygon": [{"x": 0, "y": 0}, {"x": 3, "y": 0}, {"x": 3, "y": 1}, {"x": 1, "y": 1}, {"x": 1, "y": 3}, {"x": 0, "y": 3}]},
+    {"id": 6, "x": 50, "y": 50, "rotation": 45, "polyline": [{"x": 0, "y": 0}, {"x": 10, "y": 0}, {"x": 10, "y": 10}]},
+    {"id": 7, "gid": 1, "x": 0, "y": 16, "width": 16, "height": 16},
+    {"id": 8, "x": 0, "y": 0, "width": 100, "height": 20, "text": {"text": "Hello"}}
+   ]}
+  ]}
+ ]
+}"#;
+
+    fn centroid(shape: &CollisionShape) -> Vec2 {
+        shape.mass_properties(1.0).centroid.into()
+    }
+
+    fn assert_map(map: &TiledMap) {
+        assert_abs_diff_eq!(Vec2::from(map.size), Vec2::new(320.0, 160.0));
+        let ids: Vec<u32> = map.objects.iter().map(|object| object.id).collect();
+        assert_eq!(ids, [1, 2, 3, 4, 5, 6, 7]);
+
+        let wall = &map.objects[0];
+        assert_eq!((wall.name.as_str(), wall.class.as_str()), ("wall", "solid"));
+        assert_eq!(
+            (wall.layer.as_str(), wall.shape.shapes().len()),
+            ("walls", 1)
+        );
+        // Rotated by 90° (clockwise on screen) around the top-left corner, then offset by the layer
+        let wall = &wall.shape.shapes()[0];
+        assert_abs_diff_eq!(
+            centroid(wall),
+            Vec2::new(9.0 + 5.0, 22.0 - 5.0),
+            epsilon = 1e-4
+        );
+        let bottom = CollisionShape::new_circle(0.1)
+            .with_transform(Transform::from_translation([14.0, 18.9]));
+        assert!(wall.is_collided_with(&bottom));
+
+        let circle = &map.objects[1].shape.shapes()[0];
+        assert_abs_diff_eq!(centroid(circle), Vec2::new(38.0, 38.0), epsilon = 1e-4);
+        assert_abs_diff_eq!(
+            circle.mass_properties(1.0).area,
+            9.0 * core::f32::consts::PI,
+            epsilon = 1e-3
+        );
+
+        // Ellipse rotated by -90°: 2 wide and 6 high
+        let ellipse = &map.objects[2].shape.shapes()[0];
+        assert_abs_diff_eq!(centroid(ellipse), Vec2::new(36.0, 32.0), epsilon = 1e-4);
+        let probe = |x, y| {
+            CollisionShape::new_circle(0.1).with_transform(Transform::from_translation([x, y]))
+        };
+        assert!(ellipse.is_collided_with(&probe(36.0, 29.2)));
+        assert!(!ellipse.is_collided_with(&probe(33.5, 32.0)));
+
+        let spawn = &map.objects[3];
+        assert_eq!(spawn.layer, "nested");
+        assert_abs_diff_eq!(
+            Vec2::from(spawn.shape.shapes()[0].transform().translation()),
+            Vec2::new(101.0, 2.0)
+        );
+
+        let polygon = &map.objects[4].shape;
+        assert_eq!(polygon.shapes().len(), 2);
+        let area: f32 = polygon
+            .shapes()
+            .iter()
+            .map(|shape| shape.mass_properties(1.0).area)
+            .sum();
+        assert_abs_diff_eq!(area, 5.0, epsilon = 1e-4);
+        assert!(polygon.is_collided_with(&probe(150.5, 52.5)));
+        assert!(!polygon.is_collided_with(&probe(152.5, 52.5)));
+
+        let polyline = &map.objects[5].shape;
+        assert_eq!(polyline.shapes().len(), 2);
+        let end = 10.0 * core::f32::consts::SQRT_2;
+        assert!(polyline.is_collided_with(&probe(150.0, 50.0 + end)));
+
+        let tile = &map.objects[6].shape.shapes()[0];
+        assert_abs_diff_eq!(centroid(tile), Vec2::new(108.0, 8.0), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn load_tmx() {
+        assert_map(&TiledMap::from_tmx(TMX).unwrap());
+    }
+
+    #[test]
+    fn load_tmj() {
+        assert_map(&TiledMap::from_tmj(TMJ).unwrap());
+    }
+
+    #[test]
+    fn y_up_transform() {
+        let map = TiledMap::from_tmx(TMX).unwrap();
+        let wall = &map.objects[0].shape.shapes()[0];
+        let wall = wall
+            .clone()
+            .with_transform(&map.y_up_transform() * wall.transform());
+        assert_abs_diff_eq!(
+            centroid(&wall),
+            Vec2::new(14.0, 160.0 - 17.0),
+            epsilon = 1e-4
+        );
+        let probe = |y| {
+            CollisionShape::new_circle(0.1).with_transform(Transform::from_translation([14.0, y]))
+        };
+        assert!(wall.is_collided_with(&probe(160.0 - 18.9)));
+        assert!(!wall.is_collided_with(&probe(160.0 - 19.5)));
+        assert!(wall.contact_with(&probe(160.0 - 18.95)).is_some());
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(TiledMap::from_tmx("<map").unwrap_err(), TiledError::Syntax);
+        assert_eq!(
+            TiledMap::from_tmx("<tileset/>").unwrap_err(),
+            TiledError::Invalid
+        );
+        assert_eq!(
+            TiledMap::from_tmx(r#"<map><objectgroup><object x="a"/></objectgroup></map>"#)
+                .unwrap_err(),
+            TiledError::Invalid
+        );
+        assert_eq!(TiledMap::from_tmj("{").unwrap_err(), TiledError::Syntax);
+        assert_eq!(
+            TiledMap::from_tmj(r#"{"type": "tileset"}"#).unwrap_err(),
+            TiledError::Invalid
+        );
+    }
+}
@@ -1,6 +1,11 @@
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+
+use core::ops::Mul;
+
 use glam::{Affine2, Mat2, Vec2};
 
-use super::{CollisionShape, Support};
+use super::{CollisionShape, Feature, Support, TransformError};
 
 /// Transform that can be used for a [`CollisionShape`]
 #[derive(Debug, Clone)]
@@ -20,9 +25,8 @@ impl Transform {
 
     /// Create a translation transform
     ///
-    /// # Panics
-    ///
-    /// Panic if the translation is not finite
+    /// The translation should be finite, see [`try_from_translation`](Self::try_from_translation)
+    /// for a constructor validating it.
     ///
     /// # Example with glam
     ///
@@ -39,9 +43,8 @@ impl Transform {
 
     /// Create a translation and rotation transform
     ///
-    /// # Panics
-    ///
-    /// Panic if the translation or angle is not finite
+    /// The translation and angle should be finite, see [`try_from_angle_translation`](Self::try_from_angle_translation)
+    /// for a constructor validating them.
     ///
     /// # Example with glam
     ///
@@ -62,11 +65,11 @@ impl Transform {
 
     /// Create a translation, rotation and scale transform
     ///
-    /// The scale must not be zero
-    ///
-    /// # Panics
+    /// A component of the scale may be zero, in which case the shape collapses to a segment
+    /// (or to a point if both components are zero).
     ///
-    /// Panic if a component of the scale is zero, or if the translation or angle is not finite
+    /// The scale, translation and angle should be finite,
+    /// see [`try_from_scale_angle_translation`](Self::try_from_scale_angle_translation) for a constructor validating them.
     ///
     /// # Example with glam
     ///
@@ -94,9 +97,276 @@ impl Transform {
         ))
     }
 
+    /// Create a transform from an arbitrary affine transformation,
+    /// given the images of the local x and y axes, and the translation
+    ///
+    /// The linear part may contain any combination of rotation, scale, mirroring and skew.
+    /// If it is singular, the shape collapses to a segment or a point.
+    ///
+    /// The values should be finite, see [`try_from_affine`](Self::try_from_affine) for a constructor validating them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use impacted::Transform;
+    /// let skew = Transform::from_affine([1.0, 0.0], [0.5, 1.0], [0.0, 2.0]);
+    /// assert_eq!(skew.transform_point([0.0, 1.0]), [0.5, 3.0]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_affine(
+        x_axis: impl Into<[f32; 2]>,
+        y_axis: impl Into<[f32; 2]>,
+        translation: impl Into<[f32; 2]>,
+    ) -> Self {
+        Self::new(Affine2::from_cols(
+            x_axis.into().into(),
+            y_axis.into().into(),
+            translation.into().into(),
+        ))
+    }
+
+    /// Same as [`from_translation`](Self::from_translation), but returns an error if the translation is not finite
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransformError::NonFinite`] if the translation is not finite
+    #[inline]
+    pub fn try_from_translation(translation: impl Into<[f32; 2]>) -> Result<Self, TransformError> {
+        let translation = translation.into();
+        ensure_finite(&translation)?;
+        Ok(Self::from_translation(translation))
+    }
+
+    /// Same as [`from_angle_translation`](Self::from_angle_translation), but returns an error if the translation
+    /// or angle is not finite
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransformError::NonFinite`] if the translation or angle is not finite
+    #[inline]
+    pub fn try_from_angle_translation(
+        angle: f32,
+        translation: impl Into<[f32; 2]>,
+    ) -> Result<Self, TransformError> {
+        let translation = translation.into();
+        ensure_finite(&[angle])?;
+        ensure_finite(&translation)?;
+        Ok(Self::from_angle_translation(angle, translation))
+    }
+
+    /// Same as [`from_scale_angle_translation`](Self::from_scale_angle_translation), but returns an error
+    /// if the scale, translation or angle is not finite
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransformError::NonFinite`] if the scale, translation or angle is not finite
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use impacted::{Transform, TransformError};
+    /// assert!(Transform::try_from_scale_angle_translation([0.0, 0.0], 0.0, [1.0, 2.0]).is_ok());
+    /// assert_eq!(
+    ///     Transform::try_from_scale_angle_translation([1.0, 1.0], f32::NAN, [1.0, 2.0]).err(),
+    ///     Some(TransformError::NonFinite),
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_from_scale_angle_translation(
+        scale: impl Into<[f32; 2]>,
+        angle: f32,
+        translation: impl Into<[f32; 2]>,
+    ) -> Result<Self, TransformError> {
+        let (scale, translation) = (scale.into(), translation.into());
+        ensure_finite(&scale)?;
+        ensure_finite(&[angle])?;
+        ensure_finite(&translation)?;
+        Ok(Self::from_scale_angle_translation(
+            scale,
+            angle,
+            translation,
+        ))
+    }
+
+    /// Returns the translation of the transform
+    #[inline]
+    #[must_use]
+    pub fn translation(&self) -> [f32; 2] {
+        self.local_to_world.translation.into()
+    }
+
+    /// Returns the rotation angle of the transform (in radians, between -π and π)
+    ///
+    /// If the transform has a skew (like a non-uniform scale composed with a rotation),
+    /// this is the angle of the local y axis.
+    #[inline]
+    #[must_use]
+    pub fn angle(&self) -> f32 {
+        let y_axis = self.local_to_world.y_axis;
+        Vec2::new(y_axis.y, -y_axis.x).to_angle()
+    }
+
+    /// Returns the scale of the transform
+    ///
+    /// The x component is negative if the transform mirrors the shape.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_ulps_eq;
+    /// use impacted::Transform;
+    /// let transform = Transform::from_scale_angle_translation([-2.0, 3.0], 0.5, [1.0, 2.0]);
+    /// assert_ulps_eq!(transform.scale()[0], -2.0);
+    /// assert_ulps_eq!(transform.scale()[1], 3.0);
+    /// assert_ulps_eq!(transform.angle(), 0.5);
+    /// assert_eq!(transform.translation(), [1.0, 2.0]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn scale(&self) -> [f32; 2] {
+        let matrix = self.local_to_world.matrix2;
+        let sign = if matrix.determinant() < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        [matrix.x_axis.length() * sign, matrix.y_axis.length()]
+    }
+
+    /// Returns the inverse transform, or `None` if the transform has a zero scale
+    #[inline]
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let inverse = self.local_to_world.inverse();
+        inverse.is_finite().then(|| Self::new(inverse))
+    }
+
+    /// Maps a point from the local space to the world space
+    #[inline]
+    #[must_use]
+    pub fn transform_point(&self, point: impl Into<[f32; 2]>) -> [f32; 2] {
+        self.local_to_world
+            .transform_point2(point.into().into())
+            .into()
+    }
+
+    /// Maps a point from the world space to the local space
+    ///
+    /// Returns `None` if the transform has a zero scale
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_ulps_eq;
+    /// use impacted::Transform;
+    /// let transform = Transform::from_scale_angle_translation([2.0, 2.0], 0.0, [1.0, 2.0]);
+    /// let world = transform.transform_point([1.0, 1.0]);
+    /// assert_eq!(world, [3.0, 4.0]);
+    /// let local = transform.inverse_transform_point(world).unwrap();
+    /// assert_ulps_eq!(local[0], 1.0);
+    /// assert_ulps_eq!(local[1], 1.0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn inverse_transform_point(&self, point: impl Into<[f32; 2]>) -> Option<[f32; 2]> {
+        let local =
+            self.world_to_local * (Vec2::from(point.into()) - self.local_to_world.translation);
+        local.is_finite().then(|| local.into())
+    }
+
+    /// Interpolates between `self` (when `t` is 0) and `other` (when `t` is 1)
+    ///
+    /// The translation and scale are interpolated linearly, and the angle along the shortest arc.
+    /// (Skews are not preserved.)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use approx::assert_ulps_eq;
+    /// use impacted::Transform;
+    /// let previous = Transform::from_angle_translation(3.0, [0.0, 0.0]);
+    /// let current = Transform::from_angle_translation(-3.0, [2.0, 0.0]);
+    /// let interpolated = previous.lerp(&current, 0.5);
+    /// assert_ulps_eq!(interpolated.translation()[0], 1.0);
+    /// assert_ulps_eq!(interpolated.angle().abs(), core::f32::consts::PI);
+    /// ```
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let angle = self.angle();
+        let delta = Vec2::from_angle(-angle)
+            .rotate(Vec2::from_angle(other.angle()))
+            .to_angle();
+        Self::new(Affine2::from_scale_angle_translation(
+            Vec2::from(self.scale()).lerp(other.scale().into(), t),
+            angle + delta * t,
+            self.local_to_world
+                .translation
+                .lerp(other.local_to_world.translation, t),
+        ))
+    }
+
+    /// Same as [`from_affine`](Self::from_affine), but returns an error if a value is not finite
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransformError::NonFinite`] if the axes or translation are not finite
+    #[inline]
+    pub fn try_from_affine(
+        x_axis: impl Into<[f32; 2]>,
+        y_axis: impl Into<[f32; 2]>,
+        translation: impl Into<[f32; 2]>,
+    ) -> Result<Self, TransformError> {
+        let (x_axis, y_axis, translation) = (x_axis.into(), y_axis.into(), translation.into());
+        ensure_finite(&x_axis)?;
+        ensure_finite(&y_axis)?;
+        ensure_finite(&translation)?;
+        Ok(Self::from_affine(x_axis, y_axis, translation))
+    }
+
     pub(crate) fn position(&self) -> Vec2 {
         self.local_to_world.translation
     }
+
+    pub(crate) fn local_to_world(&self) -> &Affine2 {
+        &self.local_to_world
+    }
+
+    /// Inverse of the linear part, not finite if the transform has a zero scale
+    pub(crate) fn world_to_local(&self) -> &Mat2 {
+        &self.world_to_local
+    }
+
+    /// Maps a world direction to the local direction in which the (untransformed) shape has the same support point
+    ///
+    /// The transpose of the linear part maximizes `dot(direction, matrix * point)` as `dot(transpose * direction, point)`,
+    /// so it is exact for any linear part, including skews and zero scales (collapsing the shape to a segment or a point)
+    pub(crate) fn direction_to_local(&self, direction: Vec2) -> Vec2 {
+        self.local_to_world.matrix2.transpose() * direction
+    }
+
+    /// Returns the scale factor if the transform preserves shapes (only rotates, uniformly scales and translates)
+    pub(crate) fn uniform_scale(&self) -> Option<f32> {
+        let (x_axis, y_axis) = (self.local_to_world.x_axis, self.local_to_world.y_axis);
+        let scale_squared = x_axis.length_squared();
+        let is_conformal = x_axis.dot(y_axis).abs() <= f32::EPSILON * scale_squared
+            && (y_axis.length_squared() - scale_squared).abs() <= f32::EPSILON * scale_squared;
+        (is_conformal && scale_squared > 0.0).then(|| x_axis.length())
+    }
+
+    /// Returns the scale on each axis if the transform keeps the axes aligned (has no rotation nor skew)
+    pub(crate) fn axis_aligned_scale(&self) -> Option<Vec2> {
+        let (x_axis, y_axis) = (self.local_to_world.x_axis, self.local_to_world.y_axis);
+        (x_axis.y == 0.0 && y_axis.x == 0.0).then(|| Vec2::new(x_axis.x, y_axis.y))
+    }
+}
+
+fn ensure_finite(values: &[f32]) -> Result<(), TransformError> {
+    if values.iter().all(|v| v.is_finite()) {
+        Ok(())
+    } else {
+        Err(TransformError::NonFinite)
+    }
 }
 
 impl Default for Transform {
@@ -110,9 +380,31 @@ impl Default for Transform {
     }
 }
 
+impl Mul for &Transform {
+    type Output = Transform;
+
+    /// Compose the transforms, so that `parent * child` maps from the local space of `child`
+    /// to the world space of `parent`
+    #[inline]
+    fn mul(self, rhs: Self) -> Transform {
+        Transform::new(self.local_to_world * rhs.local_to_world)
+    }
+}
+
+impl Mul for Transform {
+    type Output = Transform;
+
+    /// Compose the transforms, so that `parent * child` maps from the local space of `child`
+    /// to the world space of `parent`
+    #[inline]
+    fn mul(self, rhs: Self) -> Transform {
+        &self * &rhs
+    }
+}
+
 impl Support<Vec2> for CollisionShape {
     fn support(&self, direction: Vec2) -> Vec2 {
-        let local_direction = self.transform.world_to_local * direction;
+        let local_direction = self.transform.direction_to_local(direction);
         let local_support = self.data.support(local_direction);
         self.transform
             .local_to_world
@@ -120,12 +412,27 @@ impl Support<Vec2> for CollisionShape {
     }
 }
 
+impl CollisionShape {
+    /// Same as [`Support::support`], but also returns the feature of the shape the point belongs to
+    pub(crate) fn support_feature(&self, direction: Vec2) -> (Vec2, Feature) {
+        let local_direction = self.transform.direction_to_local(direction);
+        let (local_support, feature) = self.data.support_feature(local_direction);
+        (
+            self.transform
+                .local_to_world
+                .transform_point2(local_support),
+            feature,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::f32::consts;
 
     use approx::assert_ulps_eq;
     use glam::Vec2;
+    use rstest::rstest;
 
     use super::*;
 
@@ -143,4 +450,117 @@ mod tests {
         assert!((3.5..4.0).contains(&support_point.x));
         assert_ulps_eq!(2.0, support_point.y);
     }
+
+    #[rstest]
+    #[case(Transform::from_scale_angle_translation([3.0, 0.5], 0.7, [1.0, -2.0]))]
+    #[case(Transform::from_affine([1.0, 0.0], [0.8, 1.0], [0.0, 1.0]))]
+    #[case(Transform::from_affine([0.5, 2.0], [-1.0, 0.3], [2.0, 0.0]))]
+    #[case(Transform::from_affine([-1.0, 0.2], [0.0, 1.5], [0.0, 0.0]))]
+    fn support_of_general_affine_transform(#[case] transform: Transform) {
+        let vertices = [[-1.0, -0.5], [1.0, -0.5], [1.0, 0.5], [-1.0, 0.5]]
+            .map(|vertex| Vec2::from(transform.transform_point(vertex)));
+        let shape = CollisionShape::new_rectangle(2.0, 1.0).with_transform(transform);
+        for angle in (0..16_u8).map(|i| f32::from(i) * 0.4) {
+            let direction = Vec2::from_angle(angle);
+            let expected = vertices
+                .into_iter()
+                .map(|vertex| vertex.dot(direction))
+                .fold(f32::NEG_INFINITY, f32::max);
+            assert_ulps_eq!(
+                shape.support(direction).dot(direction),
+                expected,
+                epsilon = 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn zero_scale_collapses_to_a_point() {
+        let shape = CollisionShape::new_rectangle(2.0, 2.0).with_transform(
+            Transform::from_scale_angle_translation(Vec2::ZERO, 0.3, Vec2::new(1., 2.)),
+        );
+        for direction in [Vec2::X, Vec2::NEG_Y, Vec2::new(1.0, -2.0)] {
+            assert_eq!(shape.support(direction), Vec2::new(1., 2.));
+        }
+    }
+
+    #[test]
+    fn zero_scale_on_one_axis_collapses_to_a_segment() {
+        let shape = CollisionShape::new_rectangle(2.0, 2.0).with_transform(
+            Transform::from_scale_angle_translation(Vec2::new(2.0, 0.0), 0.0, Vec2::ZERO),
+        );
+        assert_eq!(shape.support(Vec2::new(1.0, 1.0)), Vec2::new(2.0, 0.0));
+        assert_eq!(shape.support(Vec2::new(-1.0, 1.0)), Vec2::new(-2.0, 0.0));
+    }
+
+    #[test]
+    fn composition() {
+        let parent =
+            Transform::from_scale_angle_translation([2.0, 2.0], consts::FRAC_PI_2, [1.0, 0.0]);
+        let child = Transform::from_translation([1.0, 0.0]);
+        let composed = &parent * &child;
+        let expected = parent.transform_point(child.transform_point([0.5, 0.0]));
+        let actual = composed.transform_point([0.5, 0.0]);
+        assert_ulps_eq!(Vec2::from(actual), Vec2::from(expected));
+        assert_ulps_eq!(Vec2::from(composed.translation()), Vec2::new(1.0, 2.0));
+        assert_ulps_eq!(composed.angle(), consts::FRAC_PI_2);
+    }
+
+    #[test]
+    fn inverse() {
+        let transform = Transform::from_scale_angle_translation([2.0, -0.5], 0.7, [1.0, 3.0]);
+        let identity = &transform * &transform.inverse().unwrap();
+        let point = Vec2::new(-2.0, 5.0);
+        assert_ulps_eq!(
+            Vec2::from(identity.transform_point(point)),
+            point,
+            epsilon = 1e-5
+        );
+        assert_ulps_eq!(
+            Vec2::from(
+                transform
+                    .inverse_transform_point(transform.transform_point(point))
+                    .unwrap()
+            ),
+            point,
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn zero_scale_has_no_inverse() {
+        let transform = Transform::from_scale_angle_translation([0.0, 1.0], 0.0, [1.0, 3.0]);
+        assert!(transform.inverse().is_none());
+        assert!(transform.inverse_transform_point([1.0, 1.0]).is_none());
+    }
+
+    #[test]
+    fn decomposition() {
+        let transform = Transform::from_scale_angle_translation([-2.0, 0.5], -2.5, [1.0, 3.0]);
+        assert_ulps_eq!(Vec2::from(transform.scale()), Vec2::new(-2.0, 0.5));
+        assert_ulps_eq!(transform.angle(), -2.5);
+        assert_ulps_eq!(Vec2::from(transform.translation()), Vec2::new(1.0, 3.0));
+    }
+
+    #[test]
+    fn lerp() {
+        let from = Transform::from_scale_angle_translation([1.0, 1.0], 0.0, [0.0, 0.0]);
+        let to = Transform::from_scale_angle_translation([3.0, 2.0], 1.0, [2.0, 4.0]);
+        let middle = from.lerp(&to, 0.5);
+        assert_ulps_eq!(Vec2::from(middle.scale()), Vec2::new(2.0, 1.5));
+        assert_ulps_eq!(middle.angle(), 0.5);
+        assert_ulps_eq!(Vec2::from(middle.translation()), Vec2::new(1.0, 2.0));
+        assert_ulps_eq!(from.lerp(&to, 1.0).angle(), 1.0);
+    }
+
+    #[test]
+    fn try_constructors_reject_non_finite_values() {
+        assert!(Transform::try_from_translation([f32::NAN, 0.0]).is_err());
+        assert!(Transform::try_from_angle_translation(f32::INFINITY, [0.0, 0.0]).is_err());
+        assert!(
+            Transform::try_from_scale_angle_translation([f32::NAN, 1.0], 0.0, [0.0, 0.0]).is_err()
+        );
+        assert!(Transform::try_from_affine([1.0, 0.0], [0.0, f32::NAN], [0.0, 0.0]).is_err());
+        assert!(Transform::try_from_angle_translation(1.0, [2.0, 0.0]).is_ok());
+    }
 }
@@ -0,0 +1,105 @@
+use nalgebra::{Affine2, Isometry2, Matrix3, Similarity2};
+
+use super::Transform;
+
+impl Transform {
+    /// Create a transform from the homogeneous matrix of a nalgebra transformation
+    fn from_homogeneous(matrix: &Matrix3<f32>) -> Self {
+        Self::from_affine(
+            [matrix[(0, 0)], matrix[(1, 0)]],
+            [matrix[(0, 1)], matrix[(1, 1)]],
+            [matrix[(0, 2)], matrix[(1, 2)]],
+        )
+    }
+}
+
+impl From<Isometry2<f32>> for Transform {
+    #[inline]
+    fn from(isometry: Isometry2<f32>) -> Self {
+        Self::from_homogeneous(&isometry.to_homogeneous())
+    }
+}
+
+impl From<Similarity2<f32>> for Transform {
+    #[inline]
+    fn from(similarity: Similarity2<f32>) -> Self {
+        Self::from_homogeneous(&similarity.to_homogeneous())
+    }
+}
+
+impl From<Affine2<f32>> for Transform {
+    #[inline]
+    fn from(affine: Affine2<f32>) -> Self {
+        Self::from_homogeneous(affine.matrix())
+    }
+}
+
+impl From<&Transform> for Affine2<f32> {
+    #[inline]
+    fn from(transform: &Transform) -> Self {
+        let affine = transform.local_to_world;
+        Affine2::from_matrix_unchecked(Matrix3::new(
+            affine.x_axis.x,
+            affine.y_axis.x,
+            affine.translation.x,
+            affine.x_axis.y,
+            affine.y_axis.y,
+            affine.translation.y,
+            0.0,
+            0.0,
+            1.0,
+        ))
+    }
+}
+
+impl From<Transform> for Affine2<f32> {
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        Self::from(&transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_ulps_eq;
+    use core::f32::consts;
+    use nalgebra::{Point2, Vector2};
+
+    use super::*;
+
+    #[test]
+    fn from_isometry() {
+        let isometry = Isometry2::new(Vector2::new(1.0, 2.0), consts::FRAC_PI_3);
+        let transform = Transform::from(isometry);
+        let expected = Transform::from_angle_translation(consts::FRAC_PI_3, [1.0, 2.0]);
+        let point = Point2::new(0.5, -3.0);
+        let actual: Point2<f32> = transform.transform_point(point).into();
+        assert_ulps_eq!(actual, isometry * point);
+        assert_ulps_eq!(
+            Vector2::from(transform.transform_point(point)),
+            Vector2::from(expected.transform_point(point))
+        );
+    }
+
+    #[test]
+    fn from_similarity() {
+        let similarity = Similarity2::new(Vector2::new(1.0, 2.0), -0.7, 2.5);
+        let point = Point2::new(0.5, -3.0);
+        let actual: Point2<f32> = Transform::from(similarity).transform_point(point).into();
+        assert_ulps_eq!(actual, similarity * point);
+    }
+
+    #[test]
+    fn affine_round_trip() {
+        let affine = Affine2::from_matrix_unchecked(Matrix3::new(
+            1.0, 0.5, 3.0, //
+            -0.2, 2.0, 4.0, //
+            0.0, 0.0, 1.0,
+        ));
+        let transform = Transform::from(affine);
+        let point = Point2::new(0.5, -3.0);
+        let actual: Point2<f32> = transform.transform_point(point).into();
+        assert_ulps_eq!(actual, affine * point);
+        assert_eq!(Affine2::from(&transform), affine);
+    }
+}
@@ -5,6 +5,7 @@ use sealed::sealed;
 use super::{__seal_shape, vector::Vec2, Point, Range, Shape};
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Aabb {
     center: Point,
     half_size: Vec2,
@@ -0,0 +1,58 @@
+use crate::v3::{Point, Vec2};
+
+impl From<mint::Vector2<f32>> for Vec2 {
+    fn from(v: mint::Vector2<f32>) -> Self {
+        Vec2::new(v.x, v.y)
+    }
+}
+
+impl From<Vec2> for mint::Vector2<f32> {
+    fn from(v: Vec2) -> Self {
+        mint::Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<mint::Point2<f32>> for Point {
+    fn from(p: mint::Point2<f32>) -> Self {
+        Self::new(p.x, p.y)
+    }
+}
+
+impl From<Point> for mint::Point2<f32> {
+    fn from(p: Point) -> Self {
+        mint::Point2 { x: p.x(), y: p.y() }
+    }
+}
+
+impl From<mint::Vector2<f32>> for Point {
+    fn from(v: mint::Vector2<f32>) -> Self {
+        Self::new(v.x, v.y)
+    }
+}
+
+impl From<Point> for mint::Vector2<f32> {
+    fn from(p: Point) -> Self {
+        mint::Vector2 { x: p.x(), y: p.y() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector_round_trip() {
+        let v = Vec2::new(1.0, 2.0);
+        let m: mint::Vector2<f32> = v.into();
+        assert_eq!(m, mint::Vector2 { x: 1.0, y: 2.0 });
+        assert_eq!(Vec2::from(m), v);
+    }
+
+    #[test]
+    fn point_round_trip() {
+        let p = Point::new(1.0, 2.0);
+        let m: mint::Point2<f32> = p.into();
+        assert_eq!(m, mint::Point2 { x: 1.0, y: 2.0 });
+        assert_eq!(Point::from(m), p);
+    }
+}
@@ -1,2 +1,4 @@
 #[cfg(feature = "unstable-v3-glam-0-24")]
 mod glam_0_24;
+#[cfg(feature = "mint")]
+mod mint;
@@ -8,6 +8,7 @@ use sealed::sealed;
 use super::{vector::Vec2, Range, Shape, __seal_shape};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point(Vec2);
 
 impl Point {
@@ -6,7 +6,7 @@ use approx::assert_abs_diff_eq;
 use glam::Vec2;
 use rstest::*;
 
-use impacted::{CollisionShape, Transform};
+use impacted::{CollisionShape, PairCache, QueryConfig, Transform};
 
 #[rstest]
 #[case(CollisionShape::new_circle(1.0), CollisionShape::new_circle(1.0))]
@@ -148,3 +148,330 @@ fn contact_penetration(
     let contact = shape1.contact_with(&shape2).unwrap();
     assert_abs_diff_eq!(contact.penetration, expected_penetration, epsilon = 0.0001);
 }
+
+#[test]
+fn contact_separates_deeply_penetrating_shapes() {
+    let segment = CollisionShape::new_segment([-1.0, -0.3], [1.5, 0.7])
+        .with_transform(Transform::from_angle_translation(4.68, [-0.46, 0.24]));
+    for i in 0..20_u8 {
+        for j in 0..20_u8 {
+            let position = Vec2::new(f32::from(i), f32::from(j)) * 0.05 - 0.5;
+            let circle = |offset: Vec2| {
+                CollisionShape::new_circle(1.0)
+                    .with_transform(Transform::from_angle_translation(3.0, position + offset))
+            };
+            let contact = circle(Vec2::ZERO).contact_with(&segment).unwrap();
+            assert!(contact.penetration > 0.0, "{position} {contact:?}");
+            let normal = Vec2::from(contact.normal);
+            assert!(
+                !circle(normal * (contact.penetration + 0.001)).is_collided_with(&segment),
+                "{position} {contact:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn contact_between_shallowly_penetrating_circles() {
+    let circle = CollisionShape::new_circle(1.0);
+    for i in 0..40_u8 {
+        let angle = f32::from(i) * 0.157;
+        for depth in [0.001, 0.0035, 0.006, 0.0085, 0.011] {
+            let position = Vec2::from_angle(angle) * (1.5 - depth);
+            let other = CollisionShape::new_circle(0.5)
+                .with_transform(Transform::from_angle_translation(angle * 0.7, position));
+            let contact = circle.contact_with(&other).unwrap();
+            assert_abs_diff_eq!(contact.penetration, depth, epsilon = 1e-4);
+            assert_abs_diff_eq!(
+                Vec2::from(contact.normal),
+                -Vec2::from_angle(angle),
+                epsilon = 1e-2
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case(
+    CollisionShape::new_circle(1.0),
+    CollisionShape::new_circle(1.0).with_transform(Transform::from_translation(Vec2::X * 3.0)),
+    1.0,
+    Vec2::new(-1.0, 0.0)
+)]
+#[case(
+    CollisionShape::new_circle(1.0),
+    CollisionShape::new_circle(1.0).with_transform(Transform::from_translation(Vec2::new(3.0, 4.0))),
+    3.0,
+    Vec2::new(-0.6, -0.8)
+)]
+#[case(
+    CollisionShape::new_rectangle(2.0, 2.0),
+    CollisionShape::new_rectangle(2.0, 2.0).with_transform(Transform::from_translation(Vec2::new(-3.0, 0.5))),
+    1.0,
+    Vec2::new(1.0, 0.0)
+)]
+#[case(
+    CollisionShape::new_rectangle(2.0, 2.0),
+    CollisionShape::new_rectangle(2.0, 2.0).with_transform(Transform::from_translation(Vec2::new(3.0, 3.0))),
+    2f32.sqrt(),
+    Vec2::new(-1.0, -1.0).normalize()
+)]
+#[case(
+    CollisionShape::new_circle(1.0),
+    CollisionShape::new_segment(Vec2::new(-1.0, 2.0), Vec2::new(1.0, 2.0)),
+    1.0,
+    Vec2::new(0.0, -1.0)
+)]
+#[case(
+    CollisionShape::new_circle(1.0),
+    CollisionShape::new_circle(1.0).with_transform(Transform::from_translation(Vec2::X * 1.95)),
+    -0.05,
+    Vec2::new(-1.0, 0.0)
+)]
+#[case(
+    CollisionShape::new_rectangle(1.0, 1.0),
+    CollisionShape::new_rectangle(1.0, 1.0).with_transform(Transform::from_translation(Vec2::X * -0.5)),
+    -0.5,
+    Vec2::new(1.0, 0.0)
+)]
+fn separation(
+    #[case] shape1: CollisionShape,
+    #[case] shape2: CollisionShape,
+    #[case] expected_distance: f32,
+    #[case] expected_normal: Vec2,
+) {
+    let separation = shape1.separation(&shape2);
+    assert_abs_diff_eq!(separation.distance, expected_distance, epsilon = 0.0001);
+    assert_abs_diff_eq!(
+        Vec2::from(separation.normal),
+        expected_normal,
+        epsilon = 0.001
+    );
+}
+
+#[rstest]
+fn separation_is_continuous(#[values(1.9, 1.99, 2.0, 2.01, 2.1)] x: f32) {
+    let shape1 = CollisionShape::new_rectangle(2.0, 2.0);
+    let shape2 = CollisionShape::new_rectangle(2.0, 2.0)
+        .with_transform(Transform::from_translation(Vec2::new(x, 0.5)));
+    let separation = shape1.separation(&shape2);
+    assert_abs_diff_eq!(separation.distance, x - 2.0, epsilon = 0.0001);
+    assert_abs_diff_eq!(Vec2::from(separation.normal), -Vec2::X, epsilon = 0.001);
+}
+
+fn slope(angle: f32) -> CollisionShape {
+    CollisionShape::new_rectangle(4.0, 4.0).with_transform(Transform::from_angle_translation(
+        angle,
+        Vec2::new(0.0, -2.3),
+    ))
+}
+
+#[rstest]
+fn contact_along_direction(#[values(-0.5, -0.3, 0.0, 0.1, 0.3, 0.6)] angle: f32) {
+    let character = CollisionShape::new_rectangle(1.0, 1.0);
+    let slope = slope(angle);
+    let minimum = character.contact_with(&slope).unwrap();
+    let contact = character.contact_along(&slope, Vec2::Y * 2.0).unwrap();
+    assert_abs_diff_eq!(Vec2::from(contact.normal), Vec2::Y, epsilon = 0.0001);
+    assert_abs_diff_eq!(
+        contact.penetration,
+        minimum.penetration / angle.cos(),
+        epsilon = 0.0001
+    );
+}
+
+#[rstest]
+#[case(0.3, 0.5, 0.3)]
+#[case(-0.3, 0.5, -0.3)]
+#[case(0.3, 0.1, 0.1)]
+#[case(-0.3, 0.1, -0.1)]
+#[case(0.3, 0.0, 0.0)]
+#[case(0.6, 4.0, 0.6)]
+fn contact_within_cone(
+    #[case] slope_angle: f32,
+    #[case] max_angle: f32,
+    #[case] expected_angle: f32,
+) {
+    let character = CollisionShape::new_rectangle(1.0, 1.0);
+    let slope = slope(slope_angle);
+    let minimum = character.contact_with(&slope).unwrap();
+    let contact = character
+        .contact_within_cone(&slope, Vec2::Y, max_angle)
+        .unwrap();
+    assert_abs_diff_eq!(
+        Vec2::from(contact.normal),
+        Vec2::from_angle(expected_angle).rotate(Vec2::Y),
+        epsilon = 0.0001
+    );
+    assert_abs_diff_eq!(
+        contact.penetration,
+        minimum.penetration / (slope_angle - expected_angle).cos(),
+        epsilon = 0.0001
+    );
+}
+
+#[test]
+fn contact_along_direction_returns_none_if_there_is_no_collision() {
+    let shape = CollisionShape::new_circle(1.0);
+    let other =
+        CollisionShape::new_circle(1.0).with_transform(Transform::from_translation(Vec2::X * 2.1));
+    assert!(shape.contact_along(&other, Vec2::Y).is_none());
+}
+
+#[test]
+fn contact_along_zero_direction_is_minimum_penetration() {
+    let shape = CollisionShape::new_circle(1.0);
+    let other =
+        CollisionShape::new_circle(1.0).with_transform(Transform::from_translation(Vec2::X * 1.5));
+    assert_eq!(
+        shape.contact_along(&other, Vec2::ZERO),
+        shape.contact_with(&other)
+    );
+}
+
+#[test]
+fn contact_along_opposite_direction_pushes_through() {
+    let shape = CollisionShape::new_rectangle(2.0, 2.0);
+    let other = CollisionShape::new_rectangle(2.0, 2.0)
+        .with_transform(Transform::from_translation(Vec2::X * 1.5));
+    let contact = shape.contact_along(&other, Vec2::X).unwrap();
+    assert_abs_diff_eq!(Vec2::from(contact.normal), Vec2::X, epsilon = 0.0001);
+    assert_abs_diff_eq!(contact.penetration, 3.5, epsilon = 0.0001);
+}
+
+#[test]
+fn contact_between_concentric_shapes() {
+    let shape1 = CollisionShape::new_rectangle(2.0, 1.0);
+    let shape2 = CollisionShape::new_rectangle(3.0, 0.5);
+    let contact = shape1.contact_with(&shape2).unwrap();
+    assert_abs_diff_eq!(contact.penetration, 0.75, epsilon = 1e-4);
+    assert_abs_diff_eq!(contact.normal[0], 0.0, epsilon = 1e-4);
+}
+
+#[rstest]
+#[case(
+    CollisionShape::new_circle(1.0),
+    CollisionShape::new_rectangle(2.0, 2.0)
+)]
+#[case(
+    CollisionShape::new_rectangle(1.0, 3.0),
+    CollisionShape::new_rectangle(2.0, 2.0)
+)]
+#[case(
+    CollisionShape::new_circle(1.0),
+    CollisionShape::new_segment(Vec2::new(0.0, -1.0), Vec2::new(0.5, 1.0))
+)]
+fn cached_queries_are_equivalent(
+    #[case] shape1: CollisionShape,
+    #[case] mut shape2: CollisionShape,
+) {
+    let mut cache = PairCache::new();
+    for step in 0..100_u8 {
+        let step = f32::from(step);
+        // Never exactly concentric, where opposite contact normals may be equally valid
+        shape2.set_transform(Transform::from_angle_translation(
+            step * 0.01,
+            Vec2::new(3.99 - step * 0.08, 0.3),
+        ));
+        assert_eq!(
+            shape1.is_collided_with_cached(&shape2, &mut cache),
+            shape1.is_collided_with(&shape2),
+            "step {step}"
+        );
+        let expected = shape1.contact_with(&shape2);
+        let actual = shape1.contact_with_cached(&shape2, &mut cache);
+        assert_eq!(actual.is_some(), expected.is_some(), "step {step}");
+        if let (Some(actual), Some(expected)) = (actual, expected) {
+            assert_abs_diff_eq!(actual.penetration, expected.penetration, epsilon = 0.0001);
+            assert_abs_diff_eq!(
+                Vec2::from(actual.normal),
+                Vec2::from(expected.normal),
+                epsilon = 0.01
+            );
+        }
+    }
+}
+
+#[rstest]
+fn queries_with_config(#[values(0, 1, 2, 10)] max_iterations: usize) {
+    let config = QueryConfig::default()
+        .with_epsilon(0.1)
+        .with_max_gjk_iterations(max_iterations)
+        .with_max_epa_iterations(max_iterations);
+    let shape1 = CollisionShape::new_segment(Vec2::ZERO, Vec2::X);
+    let shape2 = CollisionShape::new_rectangle(2.0, 2.0).with_transform(
+        Transform::from_scale_angle_translation(Vec2::new(1.0, 1e-6), 0.3, Vec2::X * 0.5),
+    );
+    let is_collided = shape1.is_collided_with_config(&shape2, &config);
+    let contact = shape1.contact_with_config(&shape2, &config);
+    assert_eq!(contact.is_some(), is_collided);
+    if let Some(contact) = contact {
+        assert!(contact.penetration.is_finite(), "{contact:?}");
+    }
+    let separation = shape1.separation_with_config(&shape2, &config);
+    assert!(separation.distance.is_finite(), "{separation:?}");
+}
+
+#[test]
+fn default_config_is_equivalent_to_default_queries() {
+    let config = QueryConfig::default();
+    let shape1 = CollisionShape::new_circle(1.0);
+    let shape2 = CollisionShape::new_rectangle(2.0, 2.0).with_transform(
+        Transform::from_scale_angle_translation(Vec2::new(1.0, 0.5), 0.3, Vec2::X),
+    );
+    assert_eq!(
+        shape1.contact_with_config(&shape2, &config),
+        shape1.contact_with(&shape2)
+    );
+    assert_eq!(
+        shape1.separation_with_config(&shape2, &config),
+        shape1.separation(&shape2)
+    );
+}
+
+#[rstest]
+fn shape_scaled_down_to_zero(#[values(1.0, 0.5, 0.1, 1e-20, 0.0)] scale: f32) {
+    let ground = CollisionShape::new_rectangle(4.0, 2.0);
+    let shape = CollisionShape::new_circle(1.0).with_transform(
+        Transform::from_scale_angle_translation([scale, scale], 0.0, [0.0, 0.5]),
+    );
+    assert!(shape.is_collided_with(&ground));
+    let contact = ground.contact_with(&shape).unwrap();
+    assert!(contact.penetration.is_finite());
+    assert!(contact.penetration >= 0.5 - 1e-4);
+}
+
+#[rstest]
+fn convex_polygon_is_equivalent_to_rectangle(
+    #[values([0.5, 0.0], [1.2, 0.7], [-0.9, 1.4], [0.0, -3.0])] position: [f32; 2],
+    #[values(0.0, 0.4, 2.0)] angle: f32,
+) {
+    let transform = Transform::from_angle_translation(angle, [0.3, -0.2]);
+    let rectangle = CollisionShape::new_rectangle(2.0, 1.0).with_transform(transform.clone());
+    let polygon =
+        CollisionShape::new_convex_polygon([[-1.0, -0.5], [1.0, -0.5], [1.0, 0.5], [-1.0, 0.5]])
+            .with_transform(transform);
+    let other =
+        CollisionShape::new_circle(1.0).with_transform(Transform::from_translation(position));
+    assert_eq!(
+        polygon.is_collided_with(&other),
+        rectangle.is_collided_with(&other)
+    );
+    let expected = rectangle.contact_with(&other);
+    let actual = polygon.contact_with(&other);
+    assert_eq!(actual.is_some(), expected.is_some());
+    if let (Some(actual), Some(expected)) = (actual, expected) {
+        assert_abs_diff_eq!(
+            Vec2::from(actual.normal),
+            Vec2::from(expected.normal),
+            epsilon = 1e-3
+        );
+        assert_abs_diff_eq!(actual.penetration, expected.penetration, epsilon = 1e-3);
+        assert_eq!(actual.features, expected.features);
+    }
+    assert_abs_diff_eq!(
+        polygon.separation(&other).distance,
+        rectangle.separation(&other).distance,
+        epsilon = 1e-3
+    );
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "deterministic")]
+
+use approx::assert_abs_diff_eq;
+use impacted::{CollisionShape, DCollisionShape, DTransform, PairCache, Transform};
+
+fn shapes() -> (CollisionShape, CollisionShape) {
+    let shape1 = CollisionShape::new_circle(1.3).with_transform(
+        Transform::from_scale_angle_translation([1.5, 0.7], 0.3, [0.1, 0.2]),
+    );
+    let shape2 = CollisionShape::new_rectangle(2.0, 1.5)
+        .with_transform(Transform::from_angle_translation(-1.1, [1.7, 0.9]));
+    (shape1, shape2)
+}
+
+/// The scalar (non-SIMD) computation gives the same results as the `f64` shapes (up to the `f32` precision)
+#[test]
+fn contact_matches_f64_reference() {
+    let (shape1, shape2) = shapes();
+    let dshape1 = DCollisionShape::new_circle(1.3).with_transform(
+        DTransform::from_scale_angle_translation([1.5, 0.7], 0.3, [0.1, 0.2]),
+    );
+    let dshape2 = DCollisionShape::new_rectangle(2.0, 1.5)
+        .with_transform(DTransform::from_angle_translation(-1.1, [1.7, 0.9]));
+    let contact = shape1
+        .contact_with_cached(&shape2, &mut PairCache::new())
+        .unwrap();
+    let expected = dshape1.contact_with(&dshape2).unwrap();
+    assert_abs_diff_eq!(
+        f64::from(contact.penetration),
+        expected.penetration,
+        epsilon = 1e-4
+    );
+    assert_abs_diff_eq!(
+        f64::from(contact.normal[0]),
+        expected.normal[0],
+        epsilon = 1e-3
+    );
+    assert_abs_diff_eq!(
+        f64::from(contact.normal[1]),
+        expected.normal[1],
+        epsilon = 1e-3
+    );
+    assert_abs_diff_eq!(
+        f64::from(shape1.separation(&shape2).distance),
+        dshape1.separation(&dshape2).distance,
+        epsilon = 1e-4
+    );
+}
+
+/// These results must be identical on every platform
+///
+/// They were computed with libm `0.2.16`, and may change with another version of libm.
+/// So this test is ignored by default, and meant to be run on each target with libm pinned:
+///
+/// ```sh
+/// cargo update -p libm --precise 0.2.16
+/// cargo test --features deterministic --test determinism -- --ignored
+/// ```
+#[test]
+#[ignore = "depends on the version of libm"]
+fn contact_is_bit_exact() {
+    let (shape1, shape2) = shapes();
+    let contact = shape1
+        .contact_with_cached(&shape2, &mut PairCache::new())
+        .unwrap();
+    let separation = shape1.separation(&shape2);
+    assert_eq!(
+        [
+            contact.normal[0].to_bits(),
+            contact.normal[1].to_bits(),
+            contact.penetration.to_bits(),
+            separation.distance.to_bits(),
+        ],
+        [3_211_011_605, 3_202_891_286, 1_064_252_448, 3_211_736_096]
+    );
+}
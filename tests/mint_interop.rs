@@ -0,0 +1,45 @@
+#![cfg(feature = "mint")]
+
+use approx::assert_ulps_eq;
+use impacted::{CollisionShape, DCollisionShape, DTransform, Transform};
+use mint::{Point2, Vector2};
+
+#[test]
+fn transform_from_mint() {
+    let transform = Transform::from_scale_angle_translation(
+        Vector2 { x: 2.0, y: 1.0 },
+        0.0,
+        Point2 { x: 1.0, y: 2.0 },
+    );
+    let point: Point2<f32> = transform.transform_point(Point2 { x: 1.0, y: 1.0 }).into();
+    assert_eq!(point, Point2 { x: 3.0, y: 3.0 });
+}
+
+#[test]
+fn shapes_from_mint() {
+    let segment =
+        CollisionShape::new_segment(Point2 { x: -1.0, y: 0.0 }, Point2 { x: 1.0, y: 0.0 });
+    let rectangle = CollisionShape::new_rectangle(2.0, 2.0)
+        .with_transform(Transform::from_translation(Vector2 { x: 0.0, y: 0.5 }));
+    assert!(segment.is_collided_with(&rectangle));
+}
+
+#[test]
+fn contact_normal_to_mint() {
+    let circle = CollisionShape::new_circle(1.0);
+    let rectangle = CollisionShape::new_rectangle(4.0, 4.0)
+        .with_transform(Transform::from_translation(Vector2 { x: 2.0, y: 0.0 }));
+    let normal: Vector2<f32> = circle.contact_with(&rectangle).unwrap().normal.into();
+    assert_ulps_eq!(normal.x, -1.0);
+    assert_ulps_eq!(normal.y, 0.0);
+}
+
+#[test]
+fn f64_shapes_from_mint() {
+    let circle = DCollisionShape::new_circle(1.0);
+    let rectangle = DCollisionShape::new_rectangle(4.0, 4.0)
+        .with_transform(DTransform::from_translation(Vector2 { x: 2.0, y: 0.0 }));
+    let normal: Vector2<f64> = circle.contact_with(&rectangle).unwrap().normal.into();
+    assert_ulps_eq!(normal.x, -1.0);
+    assert_ulps_eq!(normal.y, 0.0);
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "nalgebra")]
+
+use approx::assert_ulps_eq;
+use impacted::{CollisionShape, Transform};
+use nalgebra::{Isometry2, Point2, Vector2};
+
+#[test]
+fn shapes_from_nalgebra() {
+    let segment = CollisionShape::new_segment(Point2::new(-1.0, 0.0), Point2::new(1.0, 0.0));
+    let rectangle = CollisionShape::new_rectangle(2.0, 2.0)
+        .with_transform(Transform::from_translation(Vector2::new(0.0, 0.5)));
+    assert!(segment.is_collided_with(&rectangle));
+}
+
+#[test]
+fn contact_to_nalgebra() {
+    let circle = CollisionShape::new_circle(1.0);
+    let rectangle = CollisionShape::new_rectangle(4.0, 4.0)
+        .with_transform(Isometry2::new(Vector2::new(2.0, 0.0), 0.3));
+    let expected = CollisionShape::new_rectangle(4.0, 4.0)
+        .with_transform(Transform::from_angle_translation(0.3, [2.0, 0.0]));
+    let normal: Vector2<f32> = circle.contact_with(&rectangle).unwrap().normal.into();
+    let expected: Vector2<f32> = circle.contact_with(&expected).unwrap().normal.into();
+    assert_ulps_eq!(normal, expected);
+}
+
+#[test]
+fn separation_to_nalgebra() {
+    let circle = CollisionShape::new_circle(1.0);
+    let other = CollisionShape::new_circle(1.0).with_transform(Isometry2::translation(0.0, 3.0));
+    let separation = circle.separation(&other);
+    let normal: Vector2<f32> = separation.normal.into();
+    assert_ulps_eq!(separation.distance, 1.0);
+    assert_ulps_eq!(normal, -Vector2::y());
+}
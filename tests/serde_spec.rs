@@ -0,0 +1,152 @@
+#![cfg(all(feature = "serde", feature = "std"))]
+
+use impacted::{
+    shapes::{Circle, ConvexPolygon, Rectangle, Segment, ShapeData},
+    CollisionShape, CompoundShape, Contact, Transform,
+};
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+#[test]
+fn shapes_round_trip() {
+    let shapes: [ShapeData; 4] = [
+        Circle::new(1.5).into(),
+        Rectangle::new(2.0, 3.0).into(),
+        Segment::new([1.0, 2.0], [3.0, 4.0]).into(),
+        ConvexPolygon::new([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]).into(),
+    ];
+    for shape in shapes {
+        let actual = round_trip(&shape);
+        assert_eq!(format!("{actual:?}"), format!("{shape:?}"));
+    }
+}
+
+#[test]
+fn transform_round_trip() {
+    let transform = Transform::from_scale_angle_translation([2.0, -0.5], 0.7, [1.0, 2.0]);
+    let actual = round_trip(&transform);
+    assert_eq!(
+        actual.transform_point([3.0, 4.0]),
+        transform.transform_point([3.0, 4.0])
+    );
+    assert_eq!(
+        actual.inverse_transform_point([3.0, 4.0]),
+        transform.inverse_transform_point([3.0, 4.0])
+    );
+}
+
+#[test]
+fn transform_format() {
+    let transform = Transform::from_translation([1.0, 2.0]);
+    assert_eq!(
+        serde_json::to_string(&transform).unwrap(),
+        r#"{"x_axis":[1.0,0.0],"y_axis":[0.0,1.0],"translation":[1.0,2.0]}"#
+    );
+}
+
+#[test]
+fn collision_shape_round_trip() {
+    let shape = CollisionShape::new_rectangle(2.0, 1.0)
+        .with_transform(Transform::from_angle_translation(0.3, [1.0, 0.5]));
+    let other = CollisionShape::new_circle(1.0);
+    let actual = round_trip(&shape);
+    assert_eq!(actual.contact_with(&other), shape.contact_with(&other));
+}
+
+#[test]
+fn compound_shape_round_trip() {
+    let compound = CompoundShape::new([
+        CollisionShape::new_circle(1.0),
+        CollisionShape::new_segment([0.0, 0.0], [2.0, 0.0]),
+    ]);
+    assert_eq!(round_trip(&compound).shapes().len(), 2);
+}
+
+#[test]
+fn contact_round_trip() {
+    let contact: Contact = CollisionShape::new_circle(1.0)
+        .contact_with(
+            &CollisionShape::new_rectangle(2.0, 2.0)
+                .with_transform(Transform::from_translation([1.5, 0.0])),
+        )
+        .unwrap();
+    assert_eq!(round_trip(&contact), contact);
+}
+
+#[test]
+fn convex_polygon_stays_convex() {
+    let polygon: ConvexPolygon =
+        serde_json::from_str(r#"{"vertices":[[0.0,0.0],[1.0,1.0],[0.2,0.2],[1.0,0.0],[0.0,1.0]]}"#)
+            .unwrap();
+    assert_eq!(polygon.vertices().count(), 4);
+}
+
+#[test]
+fn rectangle_format() {
+    let rect = Rectangle::new(2.0, 3.0);
+    assert_eq!(
+        serde_json::to_string(&rect).unwrap(),
+        r#"{"width":2.0,"height":3.0}"#
+    );
+    let rect: Rectangle = serde_json::from_str(r#"{"width":-2.0,"height":3.0}"#).unwrap();
+    assert_eq!(rect.half_extents(), [1.0, 1.5]);
+}
+
+#[test]
+fn rectangle_requires_width_and_height() {
+    assert!(serde_json::from_str::<Rectangle>(r#"{"half_extents":[-1.0,2.0]}"#).is_err());
+}
+
+#[test]
+fn circle_rejects_negative_radius() {
+    assert!(serde_json::from_str::<Circle>(r#"{"radius":-1.0}"#).is_err());
+    assert!(serde_json::from_str::<ShapeData>(r#"{"Circle":{"radius":-1.0}}"#).is_err());
+    assert_eq!(
+        serde_json::from_str::<Circle>(r#"{"radius":0.0}"#).unwrap(),
+        Circle::new(0.0)
+    );
+}
+
+#[test]
+fn segment_format() {
+    let segment = Segment::new([1.0, 2.0], [3.0, 4.0]);
+    assert_eq!(
+        serde_json::to_string(&segment).unwrap(),
+        r#"{"p1":[1.0,2.0],"p2":[3.0,4.0]}"#
+    );
+}
+
+#[test]
+fn segment_rejects_non_finite_points() {
+    // Out of the `f32` range, so parsed as an infinity
+    assert!(serde_json::from_str::<Segment>(r#"{"p1":[1e39,0.0],"p2":[1.0,0.0]}"#).is_err());
+    assert!(
+        serde_json::from_str::<ShapeData>(r#"{"Segment":{"p1":[0.0,0.0],"p2":[1.0,-1e39]}}"#)
+            .is_err()
+    );
+}
+
+#[test]
+fn convex_polygon_rejects_non_finite_vertices() {
+    assert!(serde_json::from_str::<ConvexPolygon>(
+        r#"{"vertices":[[0.0,0.0],[1e39,0.0],[0.0,1.0]]}"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<ShapeData>(
+        r#"{"ConvexPolygon":{"vertices":[[0.0,0.0],[1.0,0.0],[0.0,-1e39]]}}"#
+    )
+    .is_err());
+}
+
+#[cfg(feature = "unstable-v3-aabb")]
+#[test]
+fn v3_round_trip() {
+    use impacted::v3::{ray_cast, Aabb, Point, Vec2};
+    let aabb = Aabb::from_size(Vec2::new(2.0, 2.0)).with_center_at(Point::new(3.0, 0.0));
+    let hit = ray_cast(Point::ORIGIN, Vec2::new(5.0, 0.0), &round_trip(&aabb)).unwrap();
+    assert_eq!(round_trip(&hit), hit);
+    assert_eq!(round_trip(&Point::new(1.0, 2.0)), Point::new(1.0, 2.0));
+    assert_eq!(round_trip(&Vec2::new(1.0, 2.0)), Vec2::new(1.0, 2.0));
+}